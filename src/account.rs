@@ -0,0 +1,208 @@
+use chrono::prelude::*;
+
+use crate::{Type, DateFormat, NumberFormat, QifError, ParseOptions, Money, Currency};
+use crate::transaction::{parse_date, read_date, read_amount, parse_lines, drop_first_character_from, is_unknown_field, unknown_fields_to_string};
+
+/// structure that represents an account header in a QIF file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Account {
+    pub name: String,
+    pub account_type: Option<Type>,
+    pub description: String,
+    pub credit_limit: Option<Money>,
+    pub statement_balance_date: Option<DateTime<Local>>,
+    pub statement_balance: Option<Money>,
+    /// the currency of the account, which is not part of the QIF format and so is not written to files.
+    pub currency: Option<Currency>,
+    /// lines that were not recognized when parsing, kept in their original order so they can be written back.
+    pub unknown_fields: Vec<String>
+}
+
+impl Account {
+    /**
+     * builder method used to create an account.
+     *
+     * Usage would look like this:
+     *
+     * ```ignore
+     * let checking = Account::builder()
+     *     .set_name("Checking")
+     *     .set_type("Bank")
+     *     .set_description("Sam Hill Credit Union")
+     *     .build().unwrap();
+     * ```
+     */
+    pub fn builder() -> AccountBuilder {
+        AccountBuilder::new()
+    }
+
+    pub fn to_string(&self, df: &DateFormat, nf: &NumberFormat) -> String {
+        let mut content = format!("N{}\r\n", self.name);
+
+        if let Some(account_type) = &self.account_type {
+            content.push_str(&format!("T{}\r\n", account_type.to_str()));
+        }
+
+        if !self.description.is_empty() {
+            content.push_str(&format!("D{}\r\n", self.description));
+        }
+
+        if let Some(credit_limit) = self.credit_limit {
+            content.push_str(&format!("L{}\r\n", nf.format(credit_limit)));
+        }
+
+        if let Some(statement_balance_date) = self.statement_balance_date {
+            content.push_str(&format!("/{}\r\n", statement_balance_date.format(df.chrono_str())));
+        }
+
+        if let Some(statement_balance) = self.statement_balance {
+            content.push_str(&format!("${}\r\n", nf.format(statement_balance)));
+        }
+
+        content.push_str(&unknown_fields_to_string(&self.unknown_fields));
+        content.push('^');
+
+        content
+    }
+
+    pub fn from_str(s: &str, df: &DateFormat, nf: &NumberFormat) -> Result<Self, QifError> {
+        Self::from_lines(s.lines(), &ParseOptions::strict(df, nf), &mut vec![])
+    }
+
+    /// parses the record from its lines, which is how the parser hands records over.
+    pub(crate) fn from_lines<'a>(lines: impl Iterator<Item = &'a str>, options: &ParseOptions, warnings: &mut Vec<QifError>) -> Result<Self, QifError> {
+        let (df, nf) = (&options.date_format, &options.number_format);
+        let lines: Vec<&str> = lines.collect();
+        let mut builder = Account::builder();
+
+        parse_lines(&lines, &options.mode, warnings, |line| {
+            match line {
+                content if content.starts_with("N") => {
+                    builder.set_name(&drop_first_character_from(content));
+                },
+                content if content.starts_with("T") => {
+                    builder.set_type(&drop_first_character_from(content));
+                },
+                content if content.starts_with("D") => {
+                    builder.set_description(&drop_first_character_from(content));
+                },
+                content if content.starts_with("L") => if let Some(credit_limit) = read_amount(content, nf)? {
+                    builder.set_credit_limit(credit_limit);
+                },
+                content if content.starts_with("/") => {
+                    builder.statement_balance_date = read_date(content, df)?;
+                },
+                content if content.starts_with("$") => if let Some(statement_balance) = read_amount(content, nf)? {
+                    builder.set_statement_balance(statement_balance);
+                },
+                content => if is_unknown_field(content) {
+                    builder.add_unknown_field(content);
+                }
+            }
+
+            Ok(())
+        })?;
+
+        builder.build().ok_or_else(|| QifError::missing_field('N', &lines))
+    }
+}
+
+pub struct AccountBuilder {
+    pub name: Option<String>,
+    pub account_type: Option<Type>,
+    pub description: Option<String>,
+    pub credit_limit: Option<Money>,
+    pub statement_balance_date: Option<DateTime<Local>>,
+    pub statement_balance: Option<Money>,
+    pub currency: Option<Currency>,
+    pub unknown_fields: Vec<String>
+}
+
+impl Default for AccountBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AccountBuilder {
+    pub fn new() -> Self {
+        AccountBuilder {
+            name: None,
+            account_type: None,
+            description: None,
+            credit_limit: None,
+            statement_balance_date: None,
+            statement_balance: None,
+            currency: None,
+            unknown_fields: vec![]
+        }
+    }
+
+    pub fn set_name(&mut self, name: &str) -> &mut Self {
+        self.name = if name.is_empty() {
+            None
+        } else {
+            Some(name.to_string())
+        };
+        self
+    }
+
+    /// sets the account type, keeping types that are not known, such as Port, as they were written.
+    pub fn set_type(&mut self, t: &str) -> &mut Self {
+        self.account_type = if t.trim().is_empty() {
+            None
+        } else {
+            Type::from(t).or_else(|| Some(Type::Other(t.to_string())))
+        };
+        self
+    }
+
+    pub fn set_description(&mut self, description: &str) -> &mut Self {
+        self.description = if description.is_empty() {
+            None
+        } else {
+            Some(description.to_string())
+        };
+        self
+    }
+
+    pub fn set_credit_limit(&mut self, credit_limit: Money) -> &mut Self {
+        self.credit_limit = Some(credit_limit);
+        self
+    }
+
+    pub fn set_statement_balance_date(&mut self, date: &str, format: &DateFormat) -> &mut Self {
+        self.statement_balance_date = parse_date(date, format);
+        self
+    }
+
+    pub fn set_statement_balance(&mut self, statement_balance: Money) -> &mut Self {
+        self.statement_balance = Some(statement_balance);
+        self
+    }
+
+    /// sets the currency from its ISO 4217 code, such as EUR.
+    pub fn set_currency(&mut self, currency: &str) -> &mut Self {
+        self.currency = Currency::from(currency);
+        self
+    }
+
+    pub fn add_unknown_field(&mut self, field: &str) -> &mut Self {
+        self.unknown_fields.push(field.to_string());
+        self
+    }
+
+    /// creates the account, provided that a name was given.
+    pub fn build(&self) -> Option<Account> {
+        self.name.clone().map(|name| Account {
+            name,
+            account_type: self.account_type.clone(),
+            description: self.description.clone().unwrap_or_default(),
+            credit_limit: self.credit_limit,
+            statement_balance_date: self.statement_balance_date,
+            statement_balance: self.statement_balance,
+            currency: self.currency,
+            unknown_fields: self.unknown_fields.clone()
+        })
+    }
+}
\ No newline at end of file
@@ -0,0 +1,25 @@
+use chrono::prelude::*;
+
+use crate::Money;
+
+/**
+ * structure that houses the loan details that can be attached to a memorized transaction.
+ * Each field corresponds to one of the numbered lines (1 through 7) of the record.
+ */
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Amortization {
+    pub first_payment_date: Option<DateTime<Local>>,
+    pub total_years: Option<u32>,
+    pub payments_made: Option<u32>,
+    pub periods_per_year: Option<u32>,
+    pub interest_rate: Option<f64>,
+    pub current_balance: Option<Money>,
+    pub original_amount: Option<Money>
+}
+
+impl Amortization {
+    /// checks if none of the amortization fields have been set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,221 @@
+use std::fmt;
+
+use crate::{DateFormat, NumberFormat, QifError, ParseOptions, Money};
+use crate::transaction::{drop_first_character_from, read_amount, parse_lines, is_unknown_field, unknown_fields_to_string};
+
+/// structure that represents an entry in the category list of a QIF file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Category {
+    pub name: String,
+    pub description: String,
+    pub tax_related: bool,
+    pub income: bool,
+    pub expense: bool,
+    pub budget_amount: Option<Money>,
+    pub tax_schedule: Option<String>,
+    /// lines that were not recognized when parsing, kept in their original order so they can be written back.
+    pub unknown_fields: Vec<String>
+}
+
+impl Category {
+    /**
+     * builder method used to create a category.
+     *
+     * Usage would look like this:
+     *
+     * ```ignore
+     * let salary = Category::builder()
+     *     .set_name("Salary")
+     *     .set_description("Pay from work")
+     *     .set_tax_related(true)
+     *     .set_income(true)
+     *     .build().unwrap();
+     * ```
+     */
+    pub fn builder() -> CategoryBuilder {
+        CategoryBuilder::new()
+    }
+
+    /// writes the category, with its budget amount in the given number format.
+    pub fn to_string(&self, nf: &NumberFormat) -> String {
+        let mut content = format!("N{}\r\n", self.name);
+
+        if !self.description.is_empty() {
+            content.push_str(&format!("D{}\r\n", self.description));
+        }
+
+        if self.tax_related {
+            content.push_str("T\r\n");
+        }
+
+        if self.income {
+            content.push_str("I\r\n");
+        }
+
+        if self.expense {
+            content.push_str("E\r\n");
+        }
+
+        if let Some(budget_amount) = self.budget_amount {
+            content.push_str(&format!("B{}\r\n", nf.format(budget_amount)));
+        }
+
+        if let Some(tax_schedule) = &self.tax_schedule {
+            content.push_str(&format!("R{}\r\n", tax_schedule));
+        }
+
+        content.push_str(&unknown_fields_to_string(&self.unknown_fields));
+        content.push('^');
+
+        content
+    }
+
+    /// parses a category whose budget amount, if it has one, is written like -1,234.56.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Self, QifError> {
+        Self::from_lines(s.lines(), &ParseOptions::strict(&DateFormat::MonthDayFullYear, &NumberFormat::default()), &mut vec![])
+    }
+
+    /// parses the record from its lines, which is how the parser hands records over.
+    pub(crate) fn from_lines<'a>(lines: impl Iterator<Item = &'a str>, options: &ParseOptions, warnings: &mut Vec<QifError>) -> Result<Self, QifError> {
+        let lines: Vec<&str> = lines.collect();
+        let mut builder = Category::builder();
+
+        parse_lines(&lines, &options.mode, warnings, |line| {
+            match line {
+                content if content.starts_with("N") => {
+                    builder.set_name(&drop_first_character_from(content));
+                },
+                content if content.starts_with("D") => {
+                    builder.set_description(&drop_first_character_from(content));
+                },
+                content if content.starts_with("T") => {
+                    builder.set_tax_related(true);
+                },
+                content if content.starts_with("I") => {
+                    builder.set_income(true);
+                },
+                content if content.starts_with("E") => {
+                    builder.set_expense(true);
+                },
+                content if content.starts_with("B") => if let Some(budget_amount) = read_amount(content, &options.number_format)? {
+                    builder.set_budget_amount(budget_amount);
+                },
+                content if content.starts_with("R") => {
+                    builder.set_tax_schedule(&drop_first_character_from(content));
+                },
+                content => if is_unknown_field(content) {
+                    builder.add_unknown_field(content);
+                }
+            }
+
+            Ok(())
+        })?;
+
+        builder.build().ok_or_else(|| QifError::missing_field('N', &lines))
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_string(&NumberFormat::default()))
+    }
+}
+
+pub struct CategoryBuilder {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub tax_related: bool,
+    pub income: bool,
+    pub expense: bool,
+    pub budget_amount: Option<Money>,
+    pub tax_schedule: Option<String>,
+    pub unknown_fields: Vec<String>
+}
+
+impl Default for CategoryBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CategoryBuilder {
+    pub fn new() -> Self {
+        CategoryBuilder {
+            name: None,
+            description: None,
+            tax_related: false,
+            income: false,
+            expense: false,
+            budget_amount: None,
+            tax_schedule: None,
+            unknown_fields: vec![]
+        }
+    }
+
+    pub fn set_name(&mut self, name: &str) -> &mut Self {
+        self.name = if name.is_empty() {
+            None
+        } else {
+            Some(name.to_string())
+        };
+        self
+    }
+
+    pub fn set_description(&mut self, description: &str) -> &mut Self {
+        self.description = if description.is_empty() {
+            None
+        } else {
+            Some(description.to_string())
+        };
+        self
+    }
+
+    pub fn set_tax_related(&mut self, tax_related: bool) -> &mut Self {
+        self.tax_related = tax_related;
+        self
+    }
+
+    pub fn set_income(&mut self, income: bool) -> &mut Self {
+        self.income = income;
+        self
+    }
+
+    pub fn set_expense(&mut self, expense: bool) -> &mut Self {
+        self.expense = expense;
+        self
+    }
+
+    pub fn set_budget_amount(&mut self, budget_amount: Money) -> &mut Self {
+        self.budget_amount = Some(budget_amount);
+        self
+    }
+
+    pub fn set_tax_schedule(&mut self, tax_schedule: &str) -> &mut Self {
+        self.tax_schedule = if tax_schedule.is_empty() {
+            None
+        } else {
+            Some(tax_schedule.to_string())
+        };
+        self
+    }
+
+    pub fn add_unknown_field(&mut self, field: &str) -> &mut Self {
+        self.unknown_fields.push(field.to_string());
+        self
+    }
+
+    /// creates the category, provided that a name was given.
+    pub fn build(&self) -> Option<Category> {
+        self.name.clone().map(|name| Category {
+            name,
+            description: self.description.clone().unwrap_or_default(),
+            tax_related: self.tax_related,
+            income: self.income,
+            expense: self.expense,
+            budget_amount: self.budget_amount,
+            tax_schedule: self.tax_schedule.clone(),
+            unknown_fields: self.unknown_fields.clone()
+        })
+    }
+}
\ No newline at end of file
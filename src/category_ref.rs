@@ -0,0 +1,105 @@
+use std::fmt;
+
+/**
+ * enumeration that houses what the category field (L or S) of a transaction refers to.
+ * 
+ * Text like Auto:Fuel is a subcategory path, [Savings] is a transfer to another account,
+ * and anything after a forward slash (e.g. Auto:Fuel/Business) is the class.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum CategoryRef {
+    Category { name: String, class: Option<String> },
+    Subcategory { path: Vec<String>, class: Option<String> },
+    Transfer { account: String, class: Option<String> }
+}
+
+impl CategoryRef {
+    pub fn from(s: &str) -> Option<Self> {
+        if s.is_empty() {
+            return None;
+        }
+
+        // account names may have a forward slash in them, so transfers are found before the class is split off.
+        if let Some((account, rest)) = s.strip_prefix('[').and_then(|transfer| transfer.split_once(']')) {
+            if rest.is_empty() || rest.starts_with('/') {
+                return Some(Self::Transfer { 
+                    account: account.to_string(), 
+                    class: rest.strip_prefix('/').map(|class| class.to_string())
+                })
+            }
+        }
+
+        let (category, class) = match s.split_once('/') {
+            Some((category, class)) => (category, Some(class.to_string())),
+            None => (s, None)
+        };
+
+        if category.contains(':') {
+            Some(Self::Subcategory { 
+                path: category.split(':').map(|name| name.to_string()).collect(), 
+                class 
+            })
+        } else {
+            Some(Self::Category { 
+                name: category.to_string(), 
+                class 
+            })
+        }
+    }
+
+    /// retrieve the category, including any parent categories, unless this is a transfer.
+    pub fn name(&self) -> Option<String> {
+        match self {
+            Self::Category { name, .. } if !name.is_empty() => Some(name.clone()),
+            Self::Subcategory { path, .. } => Some(path.join(":")),
+            _ => None
+        }
+    }
+
+    /// retrieve the class, if there is one.
+    pub fn class(&self) -> Option<&str> {
+        match self {
+            Self::Category { class, .. } |
+            Self::Subcategory { class, .. } |
+            Self::Transfer { class, .. } => class.as_deref().filter(|class| !class.is_empty())
+        }
+    }
+
+    /// checks if this refers to another account, rather than a category.
+    pub fn is_transfer(&self) -> bool {
+        matches!(self, Self::Transfer { .. })
+    }
+
+    /// retrieve the name of the account money is transferred to or from, if this is a transfer.
+    pub fn transfer_account(&self) -> Option<&str> {
+        match self {
+            Self::Transfer { account, .. } => Some(account),
+            _ => None
+        }
+    }
+}
+
+impl fmt::Display for CategoryRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let class = match self {
+            Self::Category { name, class } => {
+                write!(f, "{}", name)?;
+                class
+            },
+            Self::Subcategory { path, class } => {
+                write!(f, "{}", path.join(":"))?;
+                class
+            },
+            Self::Transfer { account, class } => {
+                write!(f, "[{}]", account)?;
+                class
+            }
+        };
+
+        if let Some(class) = class {
+            write!(f, "/{}", class)?;
+        }
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,57 @@
+use std::fmt;
+
+/**
+ * enumeration that houses the possible values of the number field of a transaction.
+ * Besides regular check numbers, Quicken uses a few named references,
+ * and anything else is kept as the text that was given.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum CheckNumber {
+    Number(u32),
+    Atm,
+    Eft,
+    Deposit,
+    Transfer,
+    Print,
+    Text(String)
+}
+
+impl CheckNumber {
+    pub fn from(s: &str) -> Option<Self> {
+        match s {
+            "" => None,
+            "ATM" => Some(Self::Atm),
+            "EFT" => Some(Self::Eft),
+            "DEP" => Some(Self::Deposit),
+            "TXFR" => Some(Self::Transfer),
+            "Print" => Some(Self::Print),
+            value => match value.parse::<u32>() {
+                // numbers with leading zeros or signs are kept as text so they are written back as is.
+                Ok(number) if number.to_string() == value => Some(Self::Number(number)),
+                _ => Some(Self::Text(value.to_string()))
+            }
+        }
+    }
+
+    /// retrieve the check number, if the field holds one.
+    pub fn number(&self) -> Option<u32> {
+        match self {
+            Self::Number(number) => Some(*number),
+            _ => None
+        }
+    }
+}
+
+impl fmt::Display for CheckNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Number(number) => write!(f, "{}", number),
+            Self::Atm => write!(f, "ATM"),
+            Self::Eft => write!(f, "EFT"),
+            Self::Deposit => write!(f, "DEP"),
+            Self::Transfer => write!(f, "TXFR"),
+            Self::Print => write!(f, "Print"),
+            Self::Text(text) => write!(f, "{}", text)
+        }
+    }
+}
\ No newline at end of file
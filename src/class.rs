@@ -0,0 +1,124 @@
+use std::fmt;
+
+use crate::QifError;
+use crate::transaction::{drop_first_character_from, is_unknown_field, unknown_fields_to_string};
+
+/// structure that represents an entry in the class list of a QIF file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Class {
+    pub name: String,
+    pub description: String,
+    /// lines that were not recognized when parsing, kept in their original order so they can be written back.
+    pub unknown_fields: Vec<String>
+}
+
+impl Class {
+    /**
+     * builder method used to create a class.
+     *
+     * Usage would look like this:
+     *
+     * ```ignore
+     * let business = Class::builder()
+     *     .set_name("Business")
+     *     .set_description("Expenses for the side business")
+     *     .build().unwrap();
+     * ```
+     */
+    pub fn builder() -> ClassBuilder {
+        ClassBuilder::new()
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Self, QifError> {
+        Self::from_lines(s.lines())
+    }
+
+    /// parses the record from its lines, which is how the parser hands records over.
+    pub(crate) fn from_lines<'a>(lines: impl Iterator<Item = &'a str>) -> Result<Self, QifError> {
+        let lines: Vec<&str> = lines.collect();
+        let mut builder = Class::builder();
+
+        for line in &lines {
+            match line {
+                content if content.starts_with("N") => {
+                    builder.set_name(&drop_first_character_from(content));
+                },
+                content if content.starts_with("D") => {
+                    builder.set_description(&drop_first_character_from(content));
+                },
+                content => if is_unknown_field(content) {
+                    builder.add_unknown_field(content);
+                }
+            }
+        }
+
+        builder.build().ok_or_else(|| QifError::missing_field('N', &lines))
+    }
+}
+
+impl fmt::Display for Class {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "N{}\r\n", self.name)?;
+
+        if !self.description.is_empty() {
+            write!(f, "D{}\r\n", self.description)?;
+        }
+
+        write!(f, "{}^", unknown_fields_to_string(&self.unknown_fields))
+    }
+}
+
+pub struct ClassBuilder {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub unknown_fields: Vec<String>
+}
+
+impl Default for ClassBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClassBuilder {
+    pub fn new() -> Self {
+        ClassBuilder {
+            name: None,
+            description: None,
+            unknown_fields: vec![]
+        }
+    }
+
+    pub fn set_name(&mut self, name: &str) -> &mut Self {
+        self.name = if name.is_empty() {
+            None
+        } else {
+            Some(name.to_string())
+        };
+        self
+    }
+
+    pub fn set_description(&mut self, description: &str) -> &mut Self {
+        self.description = if description.is_empty() {
+            None
+        } else {
+            Some(description.to_string())
+        };
+        self
+    }
+
+    pub fn add_unknown_field(&mut self, field: &str) -> &mut Self {
+        self.unknown_fields.push(field.to_string());
+        self
+    }
+
+    /// creates the class, provided that a name was given.
+    pub fn build(&self) -> Option<Class> {
+        self.name.clone().map(|name| Class {
+            name,
+            description: self.description.clone().unwrap_or_default(),
+            unknown_fields: self.unknown_fields.clone()
+        })
+    }
+}
\ No newline at end of file
@@ -0,0 +1,36 @@
+use std::fmt;
+
+/**
+ * structure that represents an ISO 4217 currency, such as EUR, GBP, or CAD.
+ *
+ * QIF files have no field for currencies,
+ * so currencies are set on accounts, sections, and exchange rates
+ * by whoever reads the file, and are not written back to it.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Currency {
+    code: [u8; 3]
+}
+
+impl Currency {
+    /// create a currency from its three letter code, in either case, returning None if it is not three letters.
+    pub fn from(code: &str) -> Option<Self> {
+        let code = code.trim().to_ascii_uppercase();
+
+        match code.as_bytes() {
+            &[first, second, third] if code.bytes().all(|letter| letter.is_ascii_uppercase()) => Some(Currency { code: [first, second, third] }),
+            _ => None
+        }
+    }
+
+    /// retrieve the three letter code of the currency, e.g. EUR.
+    pub fn to_str(&self) -> &str {
+        std::str::from_utf8(&self.code).unwrap_or_default()
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,66 @@
+use std::fmt;
+
+use crate::{Currency, CurrencyError, Money};
+
+/**
+ * structure that represents an amount of money in a known currency.
+ *
+ * Amounts in different currencies cannot be added or subtracted,
+ * and have to be converted with an ExchangeRate or RateTable first.
+ *
+ * ```ignore
+ * let euros = CurrencyAmount::new(Money::from("12.50").unwrap(), Currency::from("EUR").unwrap());
+ * let pounds = CurrencyAmount::new(Money::from("10.00").unwrap(), Currency::from("GBP").unwrap());
+ *
+ * assert!(euros.checked_add(pounds).is_err());
+ * ```
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CurrencyAmount {
+    pub amount: Money,
+    pub currency: Currency
+}
+
+impl CurrencyAmount {
+    pub fn new(amount: Money, currency: Currency) -> Self {
+        CurrencyAmount { amount, currency }
+    }
+
+    /// create a zero amount in the given currency, which is where totals start.
+    pub fn zero(currency: Currency) -> Self {
+        Self::new(Money::zero(), currency)
+    }
+
+    /// adds an amount in the same currency, giving an error if the currency is different or the total overflows.
+    pub fn checked_add(&self, other: CurrencyAmount) -> Result<Self, CurrencyError> {
+        self.check_currency(&other)?;
+
+        self.amount.checked_add(other.amount)
+            .map(|amount| Self::new(amount, self.currency))
+            .ok_or(CurrencyError::Overflow)
+    }
+
+    /// subtracts an amount in the same currency, giving an error if the currency is different or the result overflows.
+    pub fn checked_sub(&self, other: CurrencyAmount) -> Result<Self, CurrencyError> {
+        self.check_currency(&other)?;
+
+        self.amount.checked_sub(other.amount)
+            .map(|amount| Self::new(amount, self.currency))
+            .ok_or(CurrencyError::Overflow)
+    }
+
+    fn check_currency(&self, other: &CurrencyAmount) -> Result<(), CurrencyError> {
+        if self.currency == other.currency {
+            Ok(())
+        } else {
+            Err(CurrencyError::Mismatch { expected: self.currency, found: other.currency })
+        }
+    }
+}
+
+/// writes the amount followed by its currency, e.g. -12.50 EUR.
+impl fmt::Display for CurrencyAmount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.amount, self.currency)
+    }
+}
\ No newline at end of file
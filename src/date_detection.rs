@@ -0,0 +1,132 @@
+use chrono::prelude::*;
+
+use crate::{Type, Header, HeaderOption, DateFormat};
+use crate::transaction::drop_first_character_from;
+
+/// the formats that are tried when looking for the date format of a file, in order of preference.
+const CANDIDATES: [DateFormat; 7] = [
+    DateFormat::MonthDayFullYear,
+    DateFormat::MonthDayShortYear,
+    DateFormat::DayMonthFullYear,
+    DateFormat::DayMonthFullYearDotted,
+    DateFormat::FullYearMonthDay,
+    DateFormat::QuickenApostrophe,
+    DateFormat::QuickenPadded
+];
+
+/**
+ * structure that describes the date format found in a QIF file.
+ *
+ * Every D field in a transaction section is checked against the known formats,
+ * and the format that reads the most of them wins.
+ * The confidence is the share of dates that format could read.
+ *
+ * When dates like 01/05/2004 could be in January or May,
+ * the formats that read them differently are listed as alternatives,
+ * unless an !Option:MDY or !Option:DMY header settles it.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct DateDetection {
+    pub format: Option<DateFormat>,
+    pub confidence: f64,
+    pub alternatives: Vec<DateFormat>,
+    pub dates_found: usize
+}
+
+impl DateDetection {
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        let (dates, day_first) = dates_and_hint_in(s);
+
+        let readings: Vec<(&DateFormat, Vec<Option<NaiveDate>>)> = CANDIDATES.iter().map(|format| {
+            (format, dates.iter().map(|date| format.parse(date).filter(is_plausible)).collect())
+        }).collect();
+
+        let best_score = readings.iter().map(|(_, reading)| score_of(reading)).max().unwrap_or(0);
+
+        if best_score == 0 {
+            return DateDetection {
+                format: day_first.map(|day_first| if day_first {
+                    DateFormat::DayMonthFullYear
+                } else {
+                    DateFormat::MonthDayFullYear
+                }),
+                confidence: 0.0,
+                alternatives: vec![],
+                dates_found: dates.len()
+            }
+        }
+
+        let mut best: Vec<&(&DateFormat, Vec<Option<NaiveDate>>)> = readings.iter()
+            .filter(|(_, reading)| score_of(reading) == best_score)
+            .collect();
+
+        if let Some(day_first) = day_first {
+            if best.iter().any(|(format, _)| is_day_first(format) == day_first) {
+                best.retain(|(format, _)| is_day_first(format) == day_first);
+            }
+        }
+
+        let (format, reading) = best[0];
+        let mut alternatives: Vec<DateFormat> = vec![];
+        let mut other_readings: Vec<&Vec<Option<NaiveDate>>> = vec![reading];
+
+        for (other_format, other_reading) in &best[1..] {
+            if !other_readings.contains(&other_reading) {
+                alternatives.push((*other_format).clone());
+                other_readings.push(other_reading);
+            }
+        }
+
+        DateDetection {
+            format: Some((*format).clone()),
+            confidence: best_score as f64 / dates.len() as f64,
+            alternatives,
+            dates_found: dates.len()
+        }
+    }
+
+    /// checks if more than one format reads the dates equally well, but gives different dates.
+    pub fn is_ambiguous(&self) -> bool {
+        !self.alternatives.is_empty()
+    }
+}
+
+/**
+ * collects the D fields of transaction sections,
+ * along with whether an !Option header says days come first.
+ * D fields in lists, like categories and accounts, are descriptions, so they are skipped.
+ */
+fn dates_and_hint_in(s: &str) -> (Vec<String>, Option<bool>) {
+    let mut dates: Vec<String> = vec![];
+    let mut day_first: Option<bool> = None;
+    let mut in_dated_section = false;
+
+    for line in s.lines() {
+        match Header::from(line) {
+            Some(Header::Option(HeaderOption::MonthDayYear)) => day_first = Some(false),
+            Some(Header::Option(HeaderOption::DayMonthYear)) => day_first = Some(true),
+            Some(Header::Option(_) | Header::Clear(_)) => {},
+            Some(Header::Type(qif_type)) => in_dated_section = qif_type != Type::Memorized,
+            Some(_) => in_dated_section = false,
+            None => if in_dated_section && line.starts_with("D") {
+                dates.push(drop_first_character_from(line));
+            }
+        }
+    }
+
+    (dates, day_first)
+}
+
+fn score_of(reading: &[Option<NaiveDate>]) -> usize {
+    reading.iter().filter(|date| date.is_some()).count()
+}
+
+/// dates far outside of living memory usually mean a four digit year was read from two digits.
+fn is_plausible(date: &NaiveDate) -> bool {
+    (1900..=2100).contains(&date.year())
+}
+
+fn is_day_first(format: &DateFormat) -> bool {
+    matches!(format, DateFormat::DayMonthFullYear | DateFormat::DayMonthFullYearDotted)
+}
\ No newline at end of file
@@ -1,8 +1,32 @@
-/// a conveniece enumeration to deal with chrono based input and output.
+use chrono::prelude::*;
+
+use crate::DateDetection;
+
+/**
+ * a conveniece enumeration to deal with chrono based input and output.
+ *
+ * Besides the common formats, this covers the dates Quicken writes,
+ * which pad the month and day with spaces and may put an apostrophe
+ * before a two digit year (e.g. " 1/ 5'04"),
+ * as well as the day first formats found in UK and European exports.
+ */
+#[derive(Clone, Debug, PartialEq)]
 pub enum DateFormat {
     MonthDayFullYear,
     MonthDayShortYear,
-    FullYearMonthDay
+    FullYearMonthDay,
+    DayMonthFullYear,
+    DayMonthFullYearDotted,
+    QuickenApostrophe,
+    QuickenPadded,
+    /// any chrono pattern, such as "%d %b %Y".
+    Custom(String),
+    /**
+     * reads two digit years in the given format so that
+     * years below the pivot are in the 2000s, and the rest in the 1900s.
+     * Without a pivot, chrono's pivot of 70 is used.
+     */
+    Pivot(Box<DateFormat>, u32)
 }
 
 impl DateFormat {
@@ -11,23 +35,95 @@ impl DateFormat {
             "mm/dd/yyyy" | "%m/%d/%Y" => Some(Self::MonthDayFullYear),
             "mm/dd/yy" | "%m/%d/%y" => Some(Self::MonthDayShortYear),
             "yyyy-mm-dd" | "%Y-%m-%d" => Some(Self::FullYearMonthDay),
+            "dd/mm/yyyy" | "%d/%m/%Y" => Some(Self::DayMonthFullYear),
+            "dd.mm.yyyy" | "%d.%m.%Y" => Some(Self::DayMonthFullYearDotted),
+            "m/d'yy" | "%_m/%_d'%y" => Some(Self::QuickenApostrophe),
+            "m/d/yyyy" | "%_m/%_d/%Y" => Some(Self::QuickenPadded),
             _ => None
         }
     }
 
+    /// looks through the dates in a QIF file to find the format they are in.
+    pub fn detect(s: &str) -> DateDetection {
+        DateDetection::from_str(s)
+    }
+
+    /// uses the given pivot for two digit years, e.g. DateFormat::QuickenApostrophe.with_pivot(50).
+    pub fn with_pivot(self, pivot: u32) -> Self {
+        match self {
+            Self::Pivot(format, _) => Self::Pivot(format, pivot),
+            format => Self::Pivot(Box::new(format), pivot)
+        }
+    }
+
     pub fn human_str(&self) -> &str {
         match self {
             Self::MonthDayFullYear => "mm/dd/yyyy",
             Self::MonthDayShortYear => "mm/dd/yy",
-            Self::FullYearMonthDay => "yyyy-mm-dd"
+            Self::FullYearMonthDay => "yyyy-mm-dd",
+            Self::DayMonthFullYear => "dd/mm/yyyy",
+            Self::DayMonthFullYearDotted => "dd.mm.yyyy",
+            Self::QuickenApostrophe => "m/d'yy",
+            Self::QuickenPadded => "m/d/yyyy",
+            Self::Custom(pattern) => pattern,
+            Self::Pivot(format, _) => format.human_str()
         }
     }
 
+    /// the chrono pattern used to write dates.
     pub fn chrono_str(&self) -> &str {
         match self {
             Self::MonthDayFullYear => "%m/%d/%Y",
             Self::MonthDayShortYear => "%m/%d/%y",
-            Self::FullYearMonthDay => "%Y-%m-%d"
+            Self::FullYearMonthDay => "%Y-%m-%d",
+            Self::DayMonthFullYear => "%d/%m/%Y",
+            Self::DayMonthFullYearDotted => "%d.%m.%Y",
+            Self::QuickenApostrophe => "%_m/%_d'%y",
+            Self::QuickenPadded => "%_m/%_d/%Y",
+            Self::Custom(pattern) => pattern,
+            Self::Pivot(format, _) => format.chrono_str()
+        }
+    }
+
+    /**
+     * parses a date in this format.
+     * Quicken dates are read without their padding,
+     * and may use either an apostrophe or a slash before the year,
+     * since Quicken only uses the apostrophe for some years.
+     */
+    pub fn parse(&self, s: &str) -> Option<NaiveDate> {
+        match self {
+            Self::Pivot(format, pivot) => format.parse_with_pivot(s, *pivot),
+            format => format.parse_with_pivot(s, 70)
+        }
+    }
+
+    fn parse_with_pivot(&self, s: &str, pivot: u32) -> Option<NaiveDate> {
+        let input = match self {
+            Self::QuickenApostrophe | Self::QuickenPadded => s.replace(' ', ""),
+            _ => s.trim().to_string()
+        };
+
+        self.reading_patterns().iter().find_map(|pattern| {
+            let date = NaiveDate::parse_from_str(&input, pattern).ok()?;
+
+            if pattern.contains("%y") {
+                let short_year = date.year().rem_euclid(100);
+                let century = if short_year < pivot as i32 { 2000 } else { 1900 };
+
+                date.with_year(century + short_year)
+            } else {
+                Some(date)
+            }
+        })
+    }
+
+    fn reading_patterns(&self) -> Vec<&str> {
+        match self {
+            Self::QuickenApostrophe => vec!["%m/%d'%y", "%m/%d/%y", "%m/%d'%Y", "%m/%d/%Y"],
+            Self::QuickenPadded => vec!["%m/%d/%Y"],
+            Self::Pivot(format, _) => format.reading_patterns(),
+            format => vec![format.chrono_str()]
         }
     }
 }
\ No newline at end of file
@@ -1,19 +1,37 @@
-use std::fmt;
+use std::{error, fmt, io};
+
+use crate::Currency;
 
 /// errors for creating transactions
 #[derive(Debug, PartialEq)]
 pub enum TransactionBuildingError {
     NoDate,
     NoVendor,
-    NoAmount
+    NoAmount,
+    NoAction,
+    NoMemorizedType
 }
 
 impl TransactionBuildingError {
+    #[allow(clippy::inherent_to_string_shadow_display)]
     pub fn to_string(&self) -> String {
         match self {
             Self::NoDate => "Date could not be found or parsed.".to_string(),
             Self::NoVendor => "Vendor not found.".to_string(),
             Self::NoAmount => "No Amount value found.".to_string(),
+            Self::NoAction => "No investment action found.".to_string(),
+            Self::NoMemorizedType => "Memorized transaction type not found.".to_string(),
+        }
+    }
+
+    /// the code of the field that was missing, e.g. D for the date.
+    pub fn field(&self) -> char {
+        match self {
+            Self::NoDate => 'D',
+            Self::NoVendor => 'P',
+            Self::NoAmount => 'T',
+            Self::NoAction => 'N',
+            Self::NoMemorizedType => 'K'
         }
     }
 }
@@ -22,4 +40,171 @@ impl fmt::Display for TransactionBuildingError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.to_string())
     }
-}
\ No newline at end of file
+}
+
+/**
+ * errors for reading QIF data.
+ *
+ * Apart from I/O errors, each error says which line it was found on (starting at 1),
+ * which record it was found in (starting at 0), and the text that caused it.
+ * When a single record is parsed on its own, the record is always 0.
+ */
+#[derive(Debug)]
+pub enum QifError {
+    Io(io::Error),
+    BadHeader { line: usize, record: usize, text: String },
+    BadDate { line: usize, record: usize, text: String },
+    BadAmount { line: usize, record: usize, text: String },
+    /// a split memo or amount (E, $, or %) that does not follow a split category (S).
+    OrphanSplitField { line: usize, record: usize, text: String },
+    MissingField { field: char, line: usize, record: usize, text: String },
+    /// a T amount and a U amount in the same record that are not the same amount once rounded.
+    AmountConflict { line: usize, record: usize, text: String },
+    /// a record that comes before any !Type, !Account, or list header, so what kind of record it is cannot be known.
+    MissingHeader { line: usize, record: usize, text: String }
+}
+
+impl QifError {
+    pub(crate) fn bad_header(text: &str) -> Self {
+        Self::BadHeader { line: 1, record: 0, text: text.to_string() }
+    }
+
+    pub(crate) fn bad_date(text: &str) -> Self {
+        Self::BadDate { line: 1, record: 0, text: text.to_string() }
+    }
+
+    pub(crate) fn bad_amount(text: &str) -> Self {
+        Self::BadAmount { line: 1, record: 0, text: text.to_string() }
+    }
+
+    pub(crate) fn orphan_split_field(text: &str) -> Self {
+        Self::OrphanSplitField { line: 1, record: 0, text: text.to_string() }
+    }
+
+    pub(crate) fn amount_conflict(text: &str) -> Self {
+        Self::AmountConflict { line: 1, record: 0, text: text.to_string() }
+    }
+
+    pub(crate) fn missing_header(text: &str) -> Self {
+        Self::MissingHeader { line: 1, record: 0, text: text.to_string() }
+    }
+
+    /// creates the error for a record that is missing a field, where the text is the whole record.
+    pub(crate) fn missing_field(field: char, lines: &[&str]) -> Self {
+        Self::MissingField { field, line: 1, record: 0, text: lines.join("\r\n") }
+    }
+
+    /**
+     * moves an error found in some lines to where those lines are in a larger text,
+     * by adding the number of lines that come before them,
+     * and setting the record they belong to.
+     */
+    pub(crate) fn within(self, lines_before: usize, record_index: usize) -> Self {
+        match self {
+            Self::Io(error) => Self::Io(error),
+            Self::BadHeader { line, text, .. } => Self::BadHeader { line: line + lines_before, record: record_index, text },
+            Self::BadDate { line, text, .. } => Self::BadDate { line: line + lines_before, record: record_index, text },
+            Self::BadAmount { line, text, .. } => Self::BadAmount { line: line + lines_before, record: record_index, text },
+            Self::OrphanSplitField { line, text, .. } => Self::OrphanSplitField { line: line + lines_before, record: record_index, text },
+            Self::MissingField { field, line, text, .. } => Self::MissingField { field, line: line + lines_before, record: record_index, text },
+            Self::AmountConflict { line, text, .. } => Self::AmountConflict { line: line + lines_before, record: record_index, text },
+            Self::MissingHeader { line, text, .. } => Self::MissingHeader { line: line + lines_before, record: record_index, text }
+        }
+    }
+
+    /// retrieve the line the error was found on, if it came from parsing.
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            Self::Io(_) => None,
+            Self::BadHeader { line, .. } |
+            Self::BadDate { line, .. } |
+            Self::BadAmount { line, .. } |
+            Self::OrphanSplitField { line, .. } |
+            Self::MissingField { line, .. } |
+            Self::AmountConflict { line, .. } |
+            Self::MissingHeader { line, .. } => Some(*line)
+        }
+    }
+
+    /// retrieve the index of the record the error was found in, if it came from parsing.
+    pub fn record(&self) -> Option<usize> {
+        match self {
+            Self::Io(_) => None,
+            Self::BadHeader { record, .. } |
+            Self::BadDate { record, .. } |
+            Self::BadAmount { record, .. } |
+            Self::OrphanSplitField { record, .. } |
+            Self::MissingField { record, .. } |
+            Self::AmountConflict { record, .. } |
+            Self::MissingHeader { record, .. } => Some(*record)
+        }
+    }
+}
+
+/**
+ * parses a single record, moving any error or warnings it gives
+ * from the lines of the record to where the record is in the whole text.
+ */
+pub(crate) fn in_record<T>(lines_before: usize, record_index: usize, warnings: &mut Vec<QifError>, parse: impl FnOnce(&mut Vec<QifError>) -> Result<T, QifError>) -> Result<T, QifError> {
+    let mut record_warnings = vec![];
+    let result = parse(&mut record_warnings).map_err(|error| error.within(lines_before, record_index));
+
+    warnings.extend(record_warnings.into_iter().map(|warning| warning.within(lines_before, record_index)));
+
+    result
+}
+
+impl fmt::Display for QifError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{}", error),
+            Self::BadHeader { line, record, text } => write!(f, "Header not understood on line {} (record {}): {}", line, record, text),
+            Self::BadDate { line, record, text } => write!(f, "Date could not be parsed on line {} (record {}): {}", line, record, text),
+            Self::BadAmount { line, record, text } => write!(f, "Amount could not be parsed on line {} (record {}): {}", line, record, text),
+            Self::OrphanSplitField { line, record, text } => write!(f, "Split field without a split category on line {} (record {}): {}", line, record, text),
+            Self::MissingField { field, line, record, .. } => write!(f, "Record starting on line {} (record {}) is missing its {} field.", line, record, field),
+            Self::AmountConflict { line, record, text } => write!(f, "T and U amounts disagree on line {} (record {}): {}", line, record, text),
+            Self::MissingHeader { line, record, text } => write!(f, "Record found before any header on line {} (record {}): {}", line, record, text)
+        }
+    }
+}
+
+impl error::Error for QifError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None
+        }
+    }
+}
+
+impl From<io::Error> for QifError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// errors for working with amounts in different currencies
+#[derive(Debug, PartialEq)]
+pub enum CurrencyError {
+    /// amounts in different currencies were combined without converting them first.
+    Mismatch { expected: Currency, found: Currency },
+    /// there is no rate to convert from one currency to another.
+    MissingRate { from: Currency, to: Currency },
+    /// a section has no currency, either of its own or from its account.
+    NoCurrency,
+    Overflow
+}
+
+impl fmt::Display for CurrencyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Mismatch { expected, found } => write!(f, "Expected an amount in {} but found one in {}.", expected, found),
+            Self::MissingRate { from, to } => write!(f, "No exchange rate found from {} to {}.", from, to),
+            Self::NoCurrency => write!(f, "No currency set for the section or its account."),
+            Self::Overflow => write!(f, "Amount is too large.")
+        }
+    }
+}
+
+impl error::Error for CurrencyError {}
\ No newline at end of file
@@ -0,0 +1,38 @@
+use crate::{Currency, CurrencyAmount, CurrencyError, Money, RoundingMode};
+
+/**
+ * structure that represents how much one unit of a currency is worth in another,
+ * so a rate from GBP to EUR of 1.17 means 1 GBP is 1.17 EUR.
+ *
+ * On a transaction, it records the rate the amount was converted at,
+ * from the currency the transaction was made in to the currency of its account.
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExchangeRate {
+    pub from: Currency,
+    pub to: Currency,
+    pub rate: Money
+}
+
+impl ExchangeRate {
+    pub fn new(from: Currency, to: Currency, rate: Money) -> Self {
+        ExchangeRate { from, to, rate }
+    }
+
+    /**
+     * converts an amount in either currency of the rate into the other,
+     * keeping the decimal places of the amount and rounding in the given mode.
+     * Gives an error if the amount is in neither currency, or the result does not fit.
+     */
+    pub fn convert(&self, amount: CurrencyAmount, rounding: RoundingMode) -> Result<CurrencyAmount, CurrencyError> {
+        let converted = if amount.currency == self.from {
+            amount.amount.checked_mul(self.rate, rounding).map(|converted| CurrencyAmount::new(converted, self.to))
+        } else if amount.currency == self.to {
+            amount.amount.checked_div(self.rate, rounding).map(|converted| CurrencyAmount::new(converted, self.from))
+        } else {
+            return Err(CurrencyError::Mismatch { expected: self.from, found: amount.currency })
+        };
+
+        converted.ok_or(CurrencyError::Overflow)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,70 @@
+use std::fmt;
+
+use crate::{Type, HeaderOption};
+
+/**
+ * enumeration for the header lines, which start with !, in a QIF file.
+ *
+ * Headers are read without regard to case or extra whitespace,
+ * so both !type:bank and "!Type: Oth L " are understood.
+ * Headers that are not understood are kept as they were written.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum Header {
+    /// a section of transactions, such as !Type:Bank.
+    Type(Type),
+    Category,
+    Class,
+    Security,
+    Prices,
+    Account,
+    Option(HeaderOption),
+    Clear(HeaderOption),
+    Unknown(String)
+}
+
+impl Header {
+    /// parses a header, returning None if the line is not a header at all.
+    pub fn from(line: &str) -> Option<Self> {
+        // files saved by some editors start with a byte order mark, which would hide the first header.
+        let line = line.trim_start_matches('\u{feff}').trim();
+        let directive = line.strip_prefix('!')?;
+
+        let (name, value) = match directive.split_once(':') {
+            Some((name, value)) => (name.trim(), value.trim()),
+            None => (directive.trim(), "")
+        };
+
+        let header = match name.to_lowercase().as_str() {
+            "type" => match value.to_lowercase().as_str() {
+                "cat" => Some(Self::Category),
+                "class" => Some(Self::Class),
+                "security" => Some(Self::Security),
+                "prices" => Some(Self::Prices),
+                _ => Type::from(value).map(Self::Type)
+            },
+            "account" if value.is_empty() => Some(Self::Account),
+            "option" => HeaderOption::from(value).map(Self::Option),
+            "clear" => HeaderOption::from(value).map(Self::Clear),
+            _ => None
+        };
+
+        Some(header.unwrap_or(Self::Unknown(line.to_string())))
+    }
+}
+
+impl fmt::Display for Header {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Type(qif_type) => write!(f, "!Type:{}", qif_type.to_str()),
+            Self::Category => write!(f, "!Type:Cat"),
+            Self::Class => write!(f, "!Type:Class"),
+            Self::Security => write!(f, "!Type:Security"),
+            Self::Prices => write!(f, "!Type:Prices"),
+            Self::Account => write!(f, "!Account"),
+            Self::Option(option) => write!(f, "!Option:{}", option.to_str()),
+            Self::Clear(option) => write!(f, "!Clear:{}", option.to_str()),
+            Self::Unknown(header) => write!(f, "{}", header)
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,30 @@
+/// enumeration for the settings that can be turned on with !Option or off with !Clear.
+#[derive(Clone, Debug, PartialEq)]
+pub enum HeaderOption {
+    AutoSwitch,
+    MonthDayYear,
+    DayMonthYear,
+    Other(String)
+}
+
+impl HeaderOption {
+    /// reads an option name without regard to case, e.g. AutoSwitch or autoswitch.
+    pub fn from(s: &str) -> Option<Self> {
+        match s.trim() {
+            "" => None,
+            option if option.eq_ignore_ascii_case("AutoSwitch") => Some(Self::AutoSwitch),
+            option if option.eq_ignore_ascii_case("MDY") => Some(Self::MonthDayYear),
+            option if option.eq_ignore_ascii_case("DMY") => Some(Self::DayMonthYear),
+            option => Some(Self::Other(option.to_string()))
+        }
+    }
+
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::AutoSwitch => "AutoSwitch",
+            Self::MonthDayYear => "MDY",
+            Self::DayMonthYear => "DMY",
+            Self::Other(option) => option
+        }
+    }
+}
\ No newline at end of file
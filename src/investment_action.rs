@@ -0,0 +1,86 @@
+/**
+ * enumeration that houses the actions an investment transaction can have.
+ * Actions that are not known are kept as is, so they can be written back out.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum InvestmentAction {
+    Buy,
+    BuyX,
+    Sell,
+    SellX,
+    Dividend,
+    DividendX,
+    ReinvestDividend,
+    InterestIncome,
+    ReinvestInterest,
+    CapitalGainLong,
+    CapitalGainShort,
+    ReinvestLongTermGain,
+    ReinvestShortTermGain,
+    SharesIn,
+    SharesOut,
+    StockSplit,
+    TransferIn,
+    TransferOut,
+    MiscellaneousExpense,
+    MiscellaneousIncome,
+    ReturnOfCapital,
+    Other(String)
+}
+
+impl InvestmentAction {
+    pub fn from(s: &str) -> Option<Self> {
+        match s {
+            "" => None,
+            "Buy" => Some(Self::Buy),
+            "BuyX" => Some(Self::BuyX),
+            "Sell" => Some(Self::Sell),
+            "SellX" => Some(Self::SellX),
+            "Div" => Some(Self::Dividend),
+            "DivX" => Some(Self::DividendX),
+            "ReinvDiv" => Some(Self::ReinvestDividend),
+            "IntInc" => Some(Self::InterestIncome),
+            "ReinvInt" => Some(Self::ReinvestInterest),
+            "CGLong" => Some(Self::CapitalGainLong),
+            "CGShort" => Some(Self::CapitalGainShort),
+            "ReinvLg" => Some(Self::ReinvestLongTermGain),
+            "ReinvSh" => Some(Self::ReinvestShortTermGain),
+            "ShrsIn" => Some(Self::SharesIn),
+            "ShrsOut" => Some(Self::SharesOut),
+            "StkSplit" => Some(Self::StockSplit),
+            "XIn" => Some(Self::TransferIn),
+            "XOut" => Some(Self::TransferOut),
+            "MiscExp" => Some(Self::MiscellaneousExpense),
+            "MiscInc" => Some(Self::MiscellaneousIncome),
+            "RtrnCap" => Some(Self::ReturnOfCapital),
+            action => Some(Self::Other(action.to_string()))
+        }
+    }
+
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Buy => "Buy",
+            Self::BuyX => "BuyX",
+            Self::Sell => "Sell",
+            Self::SellX => "SellX",
+            Self::Dividend => "Div",
+            Self::DividendX => "DivX",
+            Self::ReinvestDividend => "ReinvDiv",
+            Self::InterestIncome => "IntInc",
+            Self::ReinvestInterest => "ReinvInt",
+            Self::CapitalGainLong => "CGLong",
+            Self::CapitalGainShort => "CGShort",
+            Self::ReinvestLongTermGain => "ReinvLg",
+            Self::ReinvestShortTermGain => "ReinvSh",
+            Self::SharesIn => "ShrsIn",
+            Self::SharesOut => "ShrsOut",
+            Self::StockSplit => "StkSplit",
+            Self::TransferIn => "XIn",
+            Self::TransferOut => "XOut",
+            Self::MiscellaneousExpense => "MiscExp",
+            Self::MiscellaneousIncome => "MiscInc",
+            Self::ReturnOfCapital => "RtrnCap",
+            Self::Other(action) => action
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,335 @@
+use chrono::prelude::*;
+
+use crate::{InvestmentAction, TransactionStatus, DateFormat, NumberFormat, TransactionBuildingError, QifError, ParseOptions, Money};
+use crate::transaction::{parse_date, read_date, read_amount, check_amounts, u_amount_to_string, parse_lines, drop_first_character_from, is_unknown_field, unknown_fields_to_string};
+
+use std::fmt;
+
+/// structure that represents a transaction in an investment section of a QIF file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InvestmentTransaction {
+    pub date: DateTime<Local>,
+    pub action: InvestmentAction,
+    pub security: Option<String>,
+    pub price: Option<Money>,
+    pub quantity: Option<Money>,
+    pub amount: Option<Money>,
+    /// the same amount as T, written by Quicken with more decimal places.
+    pub u_amount: Option<Money>,
+    pub commission: Option<Money>,
+    pub payee: Option<String>,
+    pub memo: String,
+    pub status: Option<TransactionStatus>,
+    pub transfer_account: Option<String>,
+    pub transfer_amount: Option<Money>,
+    pub unknown_fields: Vec<String>
+}
+
+impl InvestmentTransaction {
+    /**
+     * This method creates a builder that will help faciliate
+     * in a creating investment transaction.
+     *
+     * Investment transactions can then be made like this:
+     *
+     * ```ignore
+     * let purchase = InvestmentTransaction::builder()
+     *     .set_date(&today.format(format.chrono_str()).to_string(), &format)
+     *     .set_action("Buy")
+     *     .set_security("Velociraptor Entertainment")
+     *     .set_price(Money::from("25.00").unwrap())
+     *     .set_quantity(Money::from("10").unwrap())
+     *     .set_commission(Money::from("4.95").unwrap())
+     *     .set_amount(Money::from("254.95").unwrap())
+     *     .build().unwrap();
+     * ```
+     */
+    pub fn builder() -> InvestmentTransactionBuilder {
+        InvestmentTransactionBuilder::new()
+    }
+
+    pub fn to_string(&self, df: &DateFormat, nf: &NumberFormat) -> String {
+        let mut content = format!("D{}\r\nN{}\r\n",
+            self.date.format(df.chrono_str()),
+            self.action.to_str());
+
+        if let Some(security) = &self.security {
+            content.push_str(&format!("Y{}\r\n", security));
+        }
+
+        if let Some(price) = self.price {
+            content.push_str(&format!("I{}\r\n", nf.format_exact(price)));
+        }
+
+        if let Some(quantity) = self.quantity {
+            content.push_str(&format!("Q{}\r\n", nf.format_exact(quantity)));
+        }
+
+        if let Some(amount) = self.amount {
+            content.push_str(&format!("T{}\r\n", nf.format(amount)));
+        }
+
+        content.push_str(&u_amount_to_string(&self.u_amount, nf));
+
+        if let Some(status) = &self.status {
+            content.push_str(&format!("C{}\r\n", status.to_str()));
+        }
+
+        if let Some(payee) = &self.payee {
+            content.push_str(&format!("P{}\r\n", payee));
+        }
+
+        if !self.memo.is_empty() {
+            content.push_str(&format!("M{}\r\n", self.memo));
+        }
+
+        if let Some(commission) = self.commission {
+            content.push_str(&format!("O{}\r\n", nf.format(commission)));
+        }
+
+        if let Some(transfer_account) = &self.transfer_account {
+            content.push_str(&format!("L{}\r\n", transfer_account));
+        }
+
+        if let Some(transfer_amount) = self.transfer_amount {
+            content.push_str(&format!("${}\r\n", nf.format(transfer_amount)));
+        }
+
+        content.push_str(&unknown_fields_to_string(&self.unknown_fields));
+        content.push('^');
+
+        content
+    }
+
+    pub fn from_str(s: &str, df: &DateFormat, nf: &NumberFormat) -> Result<Self, QifError> {
+        Self::from_lines(s.lines(), &ParseOptions::strict(df, nf), &mut vec![])
+    }
+
+    /// parses the record from its lines, which is how the parser hands records over.
+    pub(crate) fn from_lines<'a>(lines: impl Iterator<Item = &'a str>, options: &ParseOptions, warnings: &mut Vec<QifError>) -> Result<Self, QifError> {
+        let (df, nf) = (&options.date_format, &options.number_format);
+        let lines: Vec<&str> = lines.collect();
+        let mut builder = InvestmentTransaction::builder();
+
+        parse_lines(&lines, &options.mode, warnings, |line| {
+            match line {
+                content if content.starts_with("D") => {
+                    builder.date = read_date(content, df)?;
+                },
+                content if content.starts_with("N") => {
+                    builder.set_action(&drop_first_character_from(content));
+                },
+                content if content.starts_with("Y") => {
+                    builder.set_security(&drop_first_character_from(content));
+                },
+                content if content.starts_with("I") => if let Some(price) = read_amount(content, nf)? {
+                    builder.set_price(price);
+                },
+                content if content.starts_with("Q") => if let Some(quantity) = read_amount(content, nf)? {
+                    builder.set_quantity(quantity);
+                },
+                content if content.starts_with("T") => if let Some(amount) = read_amount(content, nf)? {
+                    builder.set_amount(amount);
+                    check_amounts(builder.amount, builder.u_amount, content)?;
+                },
+                content if content.starts_with("U") => if let Some(u_amount) = read_amount(content, nf)? {
+                    builder.set_u_amount(u_amount);
+                    check_amounts(builder.amount, builder.u_amount, content)?;
+                },
+                content if content.starts_with("C") => {
+                    builder.set_status(&drop_first_character_from(content));
+                },
+                content if content.starts_with("P") => {
+                    builder.set_payee(&drop_first_character_from(content));
+                },
+                content if content.starts_with("M") => {
+                    builder.set_memo(&drop_first_character_from(content));
+                },
+                content if content.starts_with("O") => if let Some(commission) = read_amount(content, nf)? {
+                    builder.set_commission(commission);
+                },
+                content if content.starts_with("L") => {
+                    builder.set_transfer_account(&drop_first_character_from(content));
+                },
+                content if content.starts_with("$") => if let Some(transfer_amount) = read_amount(content, nf)? {
+                    builder.set_transfer_amount(transfer_amount);
+                },
+                content => if is_unknown_field(content) {
+                    builder.add_unknown_field(content);
+                }
+            }
+
+            Ok(())
+        })?;
+
+        builder.build().map_err(|error| QifError::missing_field(error.field(), &lines))
+    }
+}
+
+impl fmt::Display for InvestmentTransaction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_string(&DateFormat::MonthDayFullYear, &NumberFormat::default()))
+    }
+}
+
+pub struct InvestmentTransactionBuilder {
+    pub date: Option<DateTime<Local>>,
+    pub action: Option<InvestmentAction>,
+    pub security: Option<String>,
+    pub price: Option<Money>,
+    pub quantity: Option<Money>,
+    pub amount: Option<Money>,
+    pub u_amount: Option<Money>,
+    pub commission: Option<Money>,
+    pub payee: Option<String>,
+    pub memo: Option<String>,
+    pub status: Option<TransactionStatus>,
+    pub transfer_account: Option<String>,
+    pub transfer_amount: Option<Money>,
+    pub unknown_fields: Vec<String>
+}
+
+impl Default for InvestmentTransactionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InvestmentTransactionBuilder {
+    pub fn new() -> Self {
+        InvestmentTransactionBuilder {
+            date: None,
+            action: None,
+            security: None,
+            price: None,
+            quantity: None,
+            amount: None,
+            u_amount: None,
+            commission: None,
+            payee: None,
+            memo: None,
+            status: None,
+            transfer_account: None,
+            transfer_amount: None,
+            unknown_fields: vec![]
+        }
+    }
+
+    // builder functions to set various fields.
+    pub fn set_date(&mut self, date: &str, format: &DateFormat) -> &mut Self {
+        self.date = parse_date(date, format);
+        self
+    }
+
+    pub fn set_action(&mut self, action: &str) -> &mut Self {
+        self.action = InvestmentAction::from(action);
+        self
+    }
+
+    pub fn set_security(&mut self, security: &str) -> &mut Self {
+        self.security = if security.is_empty() {
+            None
+        } else {
+            Some(security.to_string())
+        };
+        self
+    }
+
+    pub fn set_price(&mut self, price: Money) -> &mut Self {
+        self.price = Some(price);
+        self
+    }
+
+    pub fn set_quantity(&mut self, quantity: Money) -> &mut Self {
+        self.quantity = Some(quantity);
+        self
+    }
+
+    pub fn set_amount(&mut self, amount: Money) -> &mut Self {
+        self.amount = Some(amount);
+        self
+    }
+
+    pub fn set_u_amount(&mut self, u_amount: Money) -> &mut Self {
+        self.u_amount = Some(u_amount);
+        self
+    }
+
+    pub fn set_commission(&mut self, commission: Money) -> &mut Self {
+        self.commission = Some(commission);
+        self
+    }
+
+    pub fn set_payee(&mut self, payee: &str) -> &mut Self {
+        self.payee = if payee.is_empty() {
+            None
+        } else {
+            Some(payee.to_string())
+        };
+        self
+    }
+
+    pub fn set_memo(&mut self, memo: &str) -> &mut Self {
+        self.memo = if memo.is_empty() {
+            None
+        } else {
+            Some(memo.to_string())
+        };
+        self
+    }
+
+    pub fn set_status(&mut self, status: &str) -> &mut Self {
+        self.status = TransactionStatus::from(status);
+        self
+    }
+
+    pub fn set_transfer_account(&mut self, transfer_account: &str) -> &mut Self {
+        self.transfer_account = if transfer_account.is_empty() {
+            None
+        } else {
+            Some(transfer_account.to_string())
+        };
+        self
+    }
+
+    pub fn set_transfer_amount(&mut self, transfer_amount: Money) -> &mut Self {
+        self.transfer_amount = Some(transfer_amount);
+        self
+    }
+
+    pub fn add_unknown_field(&mut self, field: &str) -> &mut Self {
+        self.unknown_fields.push(field.to_string());
+        self
+    }
+
+    /**
+     * this function is used to actually create the investment transaction.
+     * It will return an error if there is no date or action provided.
+     */
+    pub fn build(&self) -> Result<InvestmentTransaction, TransactionBuildingError> {
+        if let Some(date) = self.date {
+            if let Some(action) = self.action.clone() {
+                Ok(InvestmentTransaction {
+                    date,
+                    action,
+                    security: self.security.clone(),
+                    price: self.price,
+                    quantity: self.quantity,
+                    amount: self.amount,
+                    u_amount: self.u_amount,
+                    commission: self.commission,
+                    payee: self.payee.clone(),
+                    memo: self.memo.clone().unwrap_or_default(),
+                    status: self.status.clone(),
+                    transfer_account: self.transfer_account.clone(),
+                    transfer_amount: self.transfer_amount,
+                    unknown_fields: self.unknown_fields.clone()
+                })
+            } else {
+                Err(TransactionBuildingError::NoAction)
+            }
+        } else {
+            Err(TransactionBuildingError::NoDate)
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,467 @@
+use chrono::prelude::*;
+
+use crate::{CategoryRef, InvoiceType, CheckNumber, LineItem, LineItemBuilder, Transaction, TransactionStatus, Split, SplitBuilder, DateFormat, NumberFormat, TransactionBuildingError, QifError, ParseOptions, Money};
+use crate::transaction::{parse_field, add_splits, address_to_string, trim_address, unknown_fields_to_string, u_amount_to_string, parse_date, parse_lines};
+
+use std::fmt;
+
+/**
+ * structure that represents an invoice in a QIF file, as exported by QuickBooks.
+ *
+ * Invoice specific fields all use two character codes starting with X
+ * (e.g. XS for a line item description), so they are checked before the fields
+ * invoices share with regular transactions, which keeps them from being read as
+ * splits (S), numbers (N), or split amounts ($).
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Invoice {
+    pub date: DateTime<Local>,
+    pub number: Option<CheckNumber>,
+    pub customer: String,
+    pub address: Vec<String>,
+    pub amount: Money,
+    /// the amount QuickBooks writes in the U field, if there was one.
+    pub u_amount: Option<Money>,
+    pub category: Option<CategoryRef>,
+    pub memo: String,
+    pub status: Option<TransactionStatus>,
+    pub splits: Vec<Split>,
+    pub invoice_type: Option<InvoiceType>,
+    pub due_date: Option<DateTime<Local>>,
+    pub ship_to_address: Option<String>,
+    pub tax_account: Option<String>,
+    pub tax_rate: Option<f64>,
+    pub tax_amount: Option<Money>,
+    pub line_items: Vec<LineItem>,
+    pub unknown_fields: Vec<String>
+}
+
+impl Invoice {
+    /**
+     * This method creates a builder that will help faciliate
+     * in a creating an invoice.
+     *
+     * Invoices can then be made like this:
+     *
+     * ```ignore
+     * let invoice = Invoice::builder()
+     *     .set_date(&today.format(format.chrono_str()).to_string(), &format)
+     *     .set_number(1001)
+     *     .set_customer("Velociraptor Entertainment")
+     *     .set_amount(Money::from("162.00").unwrap())
+     *     .set_due_date(&due.format(format.chrono_str()).to_string(), &format)
+     *     .add_line_item(consultation)
+     *     .build().unwrap();
+     * ```
+     */
+    pub fn builder() -> InvoiceBuilder {
+        InvoiceBuilder::new()
+    }
+
+    pub fn to_string(&self, df: &DateFormat, nf: &NumberFormat) -> String {
+        let mut content = format!("D{}\r\nT{}\r\n{}C{}\r\nN{}\r\nP{}\r\nM{}\r\n{}L{}\r\n",
+            self.date.format(df.chrono_str()),
+            nf.format(self.amount),
+            u_amount_to_string(&self.u_amount, nf),
+            if let Some(status) = &self.status {
+                status.to_str()
+            } else {
+                ""
+            },
+            if let Some(number) = &self.number {
+                number.to_string()
+            } else {
+                String::default()
+            },
+            self.customer,
+            self.memo,
+            address_to_string(&self.address),
+            self.category.as_ref().map(|category| category.to_string()).unwrap_or_default());
+
+        if let Some(invoice_type) = &self.invoice_type {
+            content.push_str(&format!("XI{}\r\n", invoice_type.to_str()));
+        }
+
+        if let Some(due_date) = self.due_date {
+            content.push_str(&format!("XE{}\r\n", due_date.format(df.chrono_str())));
+        }
+
+        if let Some(ship_to_address) = &self.ship_to_address {
+            content.push_str(&format!("XA{}\r\n", ship_to_address));
+        }
+
+        if let Some(tax_account) = &self.tax_account {
+            content.push_str(&format!("XC{}\r\n", tax_account));
+        }
+
+        if let Some(tax_rate) = self.tax_rate {
+            content.push_str(&format!("XR{}\r\n", nf.format_value(tax_rate)));
+        }
+
+        if let Some(tax_amount) = self.tax_amount {
+            content.push_str(&format!("XT{}\r\n", nf.format(tax_amount)));
+        }
+
+        for line_item in &self.line_items {
+            content.push_str(&format!("{}\r\n", line_item.to_string(nf)));
+        }
+
+        for split in &self.splits {
+            content.push_str(&format!("{}\r\n", split.to_string(nf)));
+        }
+
+        content.push_str(&unknown_fields_to_string(&self.unknown_fields));
+        content.push('^');
+
+        content
+    }
+
+    pub fn from_str(s: &str, df: &DateFormat, nf: &NumberFormat) -> Result<Self, QifError> {
+        Self::from_lines(s.lines(), &ParseOptions::strict(df, nf), &mut vec![])
+    }
+
+    /// parses the record from its lines, which is how the parser hands records over.
+    pub(crate) fn from_lines<'a>(lines: impl Iterator<Item = &'a str>, options: &ParseOptions, warnings: &mut Vec<QifError>) -> Result<Self, QifError> {
+        let (df, nf) = (&options.date_format, &options.number_format);
+        let lines: Vec<&str> = lines.collect();
+        let mut builder = Invoice::builder();
+        let mut transaction_builder = Transaction::builder();
+        let mut split_builders: Vec<SplitBuilder> = vec![];
+        let mut line_item_builders: Vec<LineItemBuilder> = vec![];
+
+        parse_lines(&lines, &options.mode, warnings, |line| {
+            match line {
+                content if content.starts_with("XI") => {
+                    builder.set_type(drop_code_from(content));
+                },
+                content if content.starts_with("XE") => if !drop_code_from(content).trim().is_empty() {
+                    builder.due_date = Some(parse_date(drop_code_from(content), df).ok_or_else(|| QifError::bad_date(content))?);
+                },
+                content if content.starts_with("XA") => {
+                    builder.set_ship_to_address(drop_code_from(content));
+                },
+                content if content.starts_with("XC") => {
+                    builder.set_tax_account(drop_code_from(content));
+                },
+                content if content.starts_with("XR") => if let Some(tax_rate) = read_value(content, |value| nf.parse_value(value))? {
+                    builder.set_tax_rate(tax_rate);
+                },
+                content if content.starts_with("XT") => if let Some(tax_amount) = read_value(content, |value| nf.parse(value))? {
+                    builder.set_tax_amount(tax_amount);
+                },
+                content if content.starts_with("XS") => {
+                    let mut line_item_builder = LineItem::builder();
+
+                    line_item_builder.set_description(drop_code_from(content));
+
+                    line_item_builders.push(line_item_builder);
+                },
+                content if content.starts_with("XN") => {
+                    current_line_item_builder(&mut line_item_builders).set_category(drop_code_from(content));
+                },
+                content if content.starts_with("X#") => if let Some(quantity) = read_value(content, |value| nf.parse(value))? {
+                    current_line_item_builder(&mut line_item_builders).set_quantity(quantity);
+                },
+                content if content.starts_with("X$") => if let Some(price) = read_value(content, |value| nf.parse(value))? {
+                    current_line_item_builder(&mut line_item_builders).set_price(price);
+                },
+                content if content.starts_with("XF") => {
+                    let taxable = !matches!(drop_code_from(content), "N" | "0" | "F");
+
+                    current_line_item_builder(&mut line_item_builders).set_taxable(taxable);
+                },
+                content => parse_field(content, &mut transaction_builder, &mut split_builders, df, nf)?
+            }
+
+            Ok(())
+        })?;
+
+        add_splits(&mut transaction_builder, split_builders);
+
+        for line_item_builder in line_item_builders {
+            if let Some(line_item) = line_item_builder.build() {
+                builder.add_line_item(line_item);
+            }
+        }
+
+        builder.date = transaction_builder.date;
+        builder.number = transaction_builder.check_number;
+        builder.customer = transaction_builder.vendor;
+        builder.address = transaction_builder.address;
+        builder.amount = transaction_builder.amount;
+        builder.u_amount = transaction_builder.u_amount;
+        builder.category = transaction_builder.category;
+        builder.memo = transaction_builder.memo;
+        builder.status = transaction_builder.status;
+        builder.splits = transaction_builder.splits;
+        builder.unknown_fields = transaction_builder.unknown_fields;
+
+        builder.build().map_err(|error| QifError::missing_field(error.field(), &lines))
+    }
+}
+
+/// invoice fields have two character codes, so both are removed to get the value.
+fn drop_code_from(s: &str) -> &str {
+    let mut characters = s.chars();
+
+    characters.next();
+    characters.next();
+
+    characters.as_str()
+}
+
+/// reads the number in an invoice field, where an empty field means there is no number.
+fn read_value<T>(line: &str, parse: impl Fn(&str) -> Option<T>) -> Result<Option<T>, QifError> {
+    let value = drop_code_from(line);
+
+    if value.trim().is_empty() {
+        Ok(None)
+    } else {
+        parse(value).map(Some).ok_or_else(|| QifError::bad_amount(line))
+    }
+}
+
+fn current_line_item_builder(line_item_builders: &mut Vec<LineItemBuilder>) -> &mut LineItemBuilder {
+    if line_item_builders.is_empty() {
+        line_item_builders.push(LineItem::builder());
+    }
+
+    line_item_builders.last_mut().unwrap()
+}
+
+impl fmt::Display for Invoice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_string(&DateFormat::MonthDayFullYear, &NumberFormat::default()))
+    }
+}
+
+pub struct InvoiceBuilder {
+    pub date: Option<DateTime<Local>>,
+    pub number: Option<CheckNumber>,
+    pub customer: Option<String>,
+    pub address: Vec<String>,
+    pub customer_as_address: bool,
+    pub amount: Option<Money>,
+    pub u_amount: Option<Money>,
+    pub category: Option<CategoryRef>,
+    pub memo: Option<String>,
+    pub status: Option<TransactionStatus>,
+    pub splits: Vec<Split>,
+    pub invoice_type: Option<InvoiceType>,
+    pub due_date: Option<DateTime<Local>>,
+    pub ship_to_address: Option<String>,
+    pub tax_account: Option<String>,
+    pub tax_rate: Option<f64>,
+    pub tax_amount: Option<Money>,
+    pub line_items: Vec<LineItem>,
+    pub unknown_fields: Vec<String>
+}
+
+impl Default for InvoiceBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InvoiceBuilder {
+    pub fn new() -> Self {
+        InvoiceBuilder {
+            date: None,
+            number: None,
+            customer: None,
+            address: vec![],
+            customer_as_address: false,
+            amount: None,
+            u_amount: None,
+            category: None,
+            memo: None,
+            status: None,
+            splits: vec![],
+            invoice_type: None,
+            due_date: None,
+            ship_to_address: None,
+            tax_account: None,
+            tax_rate: None,
+            tax_amount: None,
+            line_items: vec![],
+            unknown_fields: vec![]
+        }
+    }
+
+    // builder functions to set various fields.
+    pub fn set_date(&mut self, date: &str, format: &DateFormat) -> &mut Self {
+        self.date = parse_date(date, format);
+        self
+    }
+
+    pub fn set_number(&mut self, number: u32) -> &mut Self {
+        self.number = if number > 0 {
+            Some(CheckNumber::Number(number))
+        } else {
+            None
+        };
+        self
+    }
+
+    pub fn set_customer(&mut self, customer: &str) -> &mut Self {
+        self.customer = if customer.is_empty() {
+            None
+        } else {
+            Some(customer.to_string())
+        };
+        self
+    }
+
+    /// sets the address to a single line, replacing any lines that were already added.
+    pub fn set_address(&mut self, address: &str) -> &mut Self {
+        self.address = if address.is_empty() {
+            vec![]
+        } else {
+            vec![String::from(address)]
+        };
+        self
+    }
+
+    pub fn add_address_line(&mut self, line: &str) -> &mut Self {
+        self.address.push(String::from(line));
+        self
+    }
+
+    /**
+     * decides if the customer should be used as the address
+     * when no address is given, which is only done when asked for.
+     */
+    pub fn set_customer_as_address(&mut self, customer_as_address: bool) -> &mut Self {
+        self.customer_as_address = customer_as_address;
+        self
+    }
+
+    pub fn set_amount(&mut self, amount: Money) -> &mut Self {
+        self.amount = Some(amount);
+        self
+    }
+
+    /// sets the amount written in the U field, which is used as the amount if there is no T amount.
+    pub fn set_u_amount(&mut self, u_amount: Money) -> &mut Self {
+        self.u_amount = Some(u_amount);
+        self
+    }
+
+    pub fn set_category(&mut self, category: &str) -> &mut Self {
+        self.category = CategoryRef::from(category);
+        self
+    }
+
+    pub fn set_memo(&mut self, memo: &str) -> &mut Self {
+        self.memo = if memo.is_empty() {
+            None
+        } else {
+            Some(String::from(memo))
+        };
+        self
+    }
+
+    pub fn set_status(&mut self, status: &str) -> &mut Self {
+        self.status = TransactionStatus::from(status);
+        self
+    }
+
+    pub fn add_split(&mut self, split: Split) -> &mut Self {
+        self.splits.push(split);
+        self
+    }
+
+    pub fn set_type(&mut self, invoice_type: &str) -> &mut Self {
+        self.invoice_type = InvoiceType::from(invoice_type);
+        self
+    }
+
+    pub fn set_due_date(&mut self, due_date: &str, format: &DateFormat) -> &mut Self {
+        self.due_date = parse_date(due_date, format);
+        self
+    }
+
+    pub fn set_ship_to_address(&mut self, ship_to_address: &str) -> &mut Self {
+        self.ship_to_address = if ship_to_address.is_empty() {
+            None
+        } else {
+            Some(ship_to_address.to_string())
+        };
+        self
+    }
+
+    pub fn set_tax_account(&mut self, tax_account: &str) -> &mut Self {
+        self.tax_account = if tax_account.is_empty() {
+            None
+        } else {
+            Some(tax_account.to_string())
+        };
+        self
+    }
+
+    pub fn set_tax_rate(&mut self, tax_rate: f64) -> &mut Self {
+        self.tax_rate = Some(tax_rate);
+        self
+    }
+
+    pub fn set_tax_amount(&mut self, tax_amount: Money) -> &mut Self {
+        self.tax_amount = Some(tax_amount);
+        self
+    }
+
+    pub fn add_line_item(&mut self, line_item: LineItem) -> &mut Self {
+        self.line_items.push(line_item);
+        self
+    }
+
+    pub fn add_unknown_field(&mut self, field: &str) -> &mut Self {
+        self.unknown_fields.push(String::from(field));
+        self
+    }
+
+    /**
+     * this function is used to actually create the invoice.
+     * It will return an error if there is no date, customer, or amount
+     * provided, which are all set with the respective setter methods.
+     */
+    pub fn build(&self) -> Result<Invoice, TransactionBuildingError> {
+        if let Some(date) = self.date {
+            if let Some(customer) = self.customer.clone() {
+                if let Some(amount) = self.amount.or(self.u_amount) {
+                    let address = trim_address(&self.address);
+
+                    Ok(Invoice {
+                        date,
+                        number: self.number.clone(),
+                        customer: customer.clone(),
+                        address: if address.is_empty() && self.customer_as_address {
+                            vec![customer]
+                        } else {
+                            address
+                        },
+                        amount,
+                        u_amount: self.u_amount,
+                        category: self.category.clone(),
+                        memo: self.memo.clone().unwrap_or_default(),
+                        status: self.status.clone(),
+                        splits: self.splits.clone(),
+                        invoice_type: self.invoice_type.clone(),
+                        due_date: self.due_date,
+                        ship_to_address: self.ship_to_address.clone(),
+                        tax_account: self.tax_account.clone(),
+                        tax_rate: self.tax_rate,
+                        tax_amount: self.tax_amount,
+                        line_items: self.line_items.clone(),
+                        unknown_fields: self.unknown_fields.clone()
+                    })
+                } else {
+                    Err(TransactionBuildingError::NoAmount)
+                }
+            } else {
+                Err(TransactionBuildingError::NoVendor)
+            }
+        } else {
+            Err(TransactionBuildingError::NoDate)
+        }
+    }
+}
\ No newline at end of file
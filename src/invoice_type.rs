@@ -0,0 +1,23 @@
+/// enumeration that houses the kinds of records an invoice section can have.
+#[derive(Clone, Debug, PartialEq)]
+pub enum InvoiceType {
+    Invoice,
+    Payment
+}
+
+impl InvoiceType {
+    pub fn from(s: &str) -> Option<Self> {
+        match s.to_string() {
+            value if value == "1" => Some(Self::Invoice),
+            value if value == "3" => Some(Self::Payment),
+            _ => None
+        }
+    }
+
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Invoice => "1",
+            Self::Payment => "3"
+        }
+    }
+}
\ No newline at end of file
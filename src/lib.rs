@@ -1,10 +1,43 @@
 mod qif_type;
 mod transaction;
 mod transaction_status;
+mod check_number;
 mod split;
+mod investment_action;
+mod investment_transaction;
+mod memorized_transaction_type;
+mod amortization;
+mod memorized_transaction;
+mod category;
+mod category_ref;
+mod class;
+mod account;
+mod security;
+mod price_quote;
+mod invoice_type;
+mod line_item;
+mod invoice;
 mod errors;
 mod date_format;
+mod date_detection;
+mod rounding_mode;
+mod money;
+mod number_format;
+mod currency;
+mod currency_amount;
+mod exchange_rate;
+mod rate_table;
+mod parse_mode;
+mod parse_options;
+mod merge_policy;
+mod header_option;
+mod header;
+mod parser;
 mod section;
+mod record;
+mod reader;
+mod writer;
+mod qif_item;
 mod qif;
 
 // expose structures and enums for simple usage
@@ -12,13 +45,57 @@ pub use qif_type::QIFType as Type;
 pub use transaction::Transaction as Transaction;
 pub use transaction::TransactionBuilder as TransactionBuilder;
 pub use transaction_status::TransactionStatus as TransactionStatus;
+pub use check_number::CheckNumber as CheckNumber;
 pub use split::Split as Split;
 pub use split::SplitBuilder as SplitBuilder;
+pub use investment_action::InvestmentAction as InvestmentAction;
+pub use investment_transaction::InvestmentTransaction as InvestmentTransaction;
+pub use investment_transaction::InvestmentTransactionBuilder as InvestmentTransactionBuilder;
+pub use memorized_transaction_type::MemorizedTransactionType as MemorizedTransactionType;
+pub use amortization::Amortization as Amortization;
+pub use memorized_transaction::MemorizedTransaction as MemorizedTransaction;
+pub use memorized_transaction::MemorizedTransactionBuilder as MemorizedTransactionBuilder;
+pub use category::Category as Category;
+pub use category::CategoryBuilder as CategoryBuilder;
+pub use category_ref::CategoryRef as CategoryRef;
+pub use class::Class as Class;
+pub use class::ClassBuilder as ClassBuilder;
+pub use account::Account as Account;
+pub use account::AccountBuilder as AccountBuilder;
+pub use security::Security as Security;
+pub use security::SecurityBuilder as SecurityBuilder;
+pub use price_quote::PriceQuote as PriceQuote;
+pub use invoice_type::InvoiceType as InvoiceType;
+pub use line_item::LineItem as LineItem;
+pub use line_item::LineItemBuilder as LineItemBuilder;
+pub use invoice::Invoice as Invoice;
+pub use invoice::InvoiceBuilder as InvoiceBuilder;
+pub use header_option::HeaderOption as HeaderOption;
+pub use header::Header as Header;
+pub use parser::Parser as Parser;
+pub use parser::Event as Event;
 pub use section::Section as Section;
+pub use record::Record as Record;
+pub use reader::QifReader as QifReader;
+pub use writer::QifWriter as QifWriter;
 pub use section::SectionBuilder as SectionBuilder;
 pub use date_format::DateFormat as DateFormat;
+pub use date_detection::DateDetection as DateDetection;
+pub use rounding_mode::RoundingMode as RoundingMode;
+pub use money::Money as Money;
+pub use number_format::NumberFormat as NumberFormat;
+pub use currency::Currency as Currency;
+pub use currency_amount::CurrencyAmount as CurrencyAmount;
+pub use exchange_rate::ExchangeRate as ExchangeRate;
+pub use rate_table::RateTable as RateTable;
+pub use parse_mode::ParseMode as ParseMode;
+pub use parse_options::ParseOptions as ParseOptions;
+pub use merge_policy::MergePolicy as MergePolicy;
+pub use qif_item::QifItem as QifItem;
 pub use qif::QIF as QIF;
 pub use errors::TransactionBuildingError as TransactionBuildingError;
+pub use errors::QifError as QifError;
+pub use errors::CurrencyError as CurrencyError;
 
 #[cfg(test)]
 mod tests {
@@ -26,6 +103,10 @@ mod tests {
     use chrono::prelude::*;
     use pretty_assertions::assert_eq;
 
+    fn money(s: &str) -> Money {
+        Money::from(s).unwrap()
+    }
+
     #[test]
     fn qif_type_outputs_to_correct_string() {
         let expected = "Bank";
@@ -76,7 +157,7 @@ mod tests {
         .set_vendor("Sam Hill Credit Union")
         .set_address("Sam Hill Credit Union")
         .set_category("Opening Balance")
-        .set_amount(500.0)
+        .set_amount(money("500.00"))
         .set_memo("Open Account")
         .set_status("*")
         .build();
@@ -96,7 +177,7 @@ mod tests {
         .set_vendor("Sam Hill Credit Union")
         .set_address("Sam Hill Credit Union")
         .set_category("Opening Balance")
-        .set_amount(500.0)
+        .set_amount(money("500.00"))
         .set_memo("Open Account")
         .set_status("*")
         .build();
@@ -138,7 +219,7 @@ mod tests {
         .set_check_number(1260)
         .set_address("Sam Hill Credit Union")
         .set_category("Opening Balance")
-        .set_amount(500.0)
+        .set_amount(money("500.00"))
         .set_memo("Open Account")
         .set_status("*")
         .build();
@@ -154,7 +235,7 @@ mod tests {
         let format = DateFormat::MonthDayFullYear;
 
         let expected = format!("D{}\r\nT{:.2}\r\nC{}\r\nN{}\r\nP{}\r\nM{}\r\nA{}\r\nL{}\r\n^", 
-        today.format("%m/%d/%Y").to_string(),
+        today.format("%m/%d/%Y"),
         500.0,
         "*",
         1260,
@@ -169,13 +250,13 @@ mod tests {
         .set_vendor("Sam Hill Credit Union")
         .set_address("Sam Hill Credit Union")
         .set_category("Opening Balance")
-        .set_amount(500.0)
+        .set_amount(money("500.00"))
         .set_memo("Open Account")
         .set_status("*")
         .build();
 
         if let Ok(transaction) = transaction {
-            assert_eq!(transaction.to_string(&format), expected);
+            assert_eq!(transaction.to_string(&format, &NumberFormat::default()), expected);
         }
     }
 
@@ -190,12 +271,12 @@ mod tests {
         .set_vendor("Sam Hill Credit Union")
         .set_address("Sam Hill Credit Union")
         .set_category("Opening Balance")
-        .set_amount(500.0)
+        .set_amount(money("500.00"))
         .set_memo("Open Account")
         .set_status("*")
         .build() {
             let text = format!("D{}\r\nT{:.2}\r\nC{}\r\nN{}\r\nP{}\r\nM{}\r\nA{}\r\nL{}\r\n^", 
-                today.format(format.chrono_str()).to_string(),
+                today.format(format.chrono_str()),
                 500.0,
                 "*",
                 1260,
@@ -204,7 +285,7 @@ mod tests {
                 "Sam Hill Credit Union",
                 "Opening Balance");
 
-                if let Ok(transaction) = Transaction::from_str(&text, &format) {
+                if let Ok(transaction) = Transaction::from_str(&text, &format, &NumberFormat::default()) {
                     assert_eq!(expected_transaction, transaction)
                 } 
         }
@@ -215,7 +296,7 @@ mod tests {
         let split = Split::builder()
         .set_category("Opening Balance")
         .set_memo("Bonus for new Account")
-        .set_amount(50.0)
+        .set_amount(money("50.00"))
         .build();
 
         assert!(split.is_some())
@@ -239,7 +320,7 @@ mod tests {
         let expected_split = Split::builder()
         .set_category("Opening Balance")
         .set_memo("Bonus for new Account")
-        .set_amount(50.0)
+        .set_amount(money("50.00"))
         .build().unwrap();
 
         let expected_transaction = Transaction::builder()
@@ -248,14 +329,14 @@ mod tests {
         .set_vendor("Sam Hill Credit Union")
         .set_address("Sam Hill Credit Union")
         .set_category("Opening Balance")
-        .set_amount(500.0)
+        .set_amount(money("500.00"))
         .set_memo("Open Account")
         .set_status("*")
         .add_split(expected_split)
         .build().unwrap();
 
         let text = format!("D{}\r\nT{:.2}\r\nC{}\r\nN{}\r\nP{}\r\nM{}\r\nA{}\r\nL{}\r\nS{}\r\nE{}\r\n${}\r\n^",
-        today.format(format.chrono_str()).to_string(),
+        today.format(format.chrono_str()),
         500.0,
         "*",
         1260,
@@ -267,7 +348,7 @@ mod tests {
         "Bonus for new Account",
         50.0);
 
-        if let Ok(transaction) = Transaction::from_str(&text, &format) {
+        if let Ok(transaction) = Transaction::from_str(&text, &format, &NumberFormat::default()) {
             assert_eq!(transaction, expected_transaction)
         }
 
@@ -281,7 +362,7 @@ mod tests {
         let expected_split = Split::builder()
         .set_category("Opening Balance")
         .set_memo("Bonus for new Account")
-        .set_amount(50.0)
+        .set_amount(money("50.00"))
         .build().unwrap();
 
         let expected_transaction = Transaction::builder()
@@ -290,14 +371,14 @@ mod tests {
         .set_vendor("Sam Hill Credit Union")
         .set_address("Sam Hill Credit Union")
         .set_category("Opening Balance")
-        .set_amount(500.0)
+        .set_amount(money("500.00"))
         .set_memo("Open Account")
         .set_status("*")
         .add_split(expected_split)
         .build().unwrap();
 
         let text = format!("D{}\r\nT{:.2}\r\nC{}\r\nN{}\r\nP{}\r\nM{}\r\nA{}\r\nL{}\r\nS{}\r\nE{}\r\n%{}\r\n^",
-        today.format(format.chrono_str()).to_string(),
+        today.format(format.chrono_str()),
         500.0,
         "*",
         1260,
@@ -309,7 +390,7 @@ mod tests {
         "Bonus for new Account",
         10);
 
-        if let Ok(transaction) = Transaction::from_str(&text, &format) {
+        if let Ok(transaction) = Transaction::from_str(&text, &format, &NumberFormat::default()) {
             assert_eq!(transaction, expected_transaction)
         }
 
@@ -321,7 +402,7 @@ mod tests {
         let format = DateFormat::MonthDayFullYear;
 
         let expected = format!("D{}\r\nT{:.2}\r\nC{}\r\nN{}\r\nP{}\r\nM{}\r\nA{}\r\nL{}\r\nS{}\r\nE{}\r\n${:.2}\r\n^",
-        today.format(format.chrono_str()).to_string(),
+        today.format(format.chrono_str()),
         500.0,
         "*",
         1260,
@@ -336,7 +417,7 @@ mod tests {
         let split = Split::builder()
         .set_category("Opening Balance")
         .set_memo("Bonus for new Account")
-        .set_amount(50.0)
+        .set_amount(money("50.00"))
         .build().unwrap();
 
         let transaction = Transaction::builder()
@@ -345,13 +426,13 @@ mod tests {
         .set_vendor("Sam Hill Credit Union")
         .set_address("Sam Hill Credit Union")
         .set_category("Opening Balance")
-        .set_amount(500.0)
+        .set_amount(money("500.00"))
         .set_memo("Open Account")
         .set_status("*")
         .add_split(split)
         .build().unwrap();
 
-        assert_eq!(expected, transaction.to_string(&format))
+        assert_eq!(expected, transaction.to_string(&format, &NumberFormat::default()))
     }
 
     #[test]
@@ -362,13 +443,13 @@ mod tests {
         let initial_expected_split = Split::builder()
         .set_category("Opening Balance")
         .set_memo("Initial Deposit")
-        .set_amount(450.0)
+        .set_amount(money("450.00"))
         .build().unwrap();
 
         let bonus_expected_split = Split::builder()
         .set_category("Opening Balance")
         .set_memo("Bonus for new Account")
-        .set_amount(50.0)
+        .set_amount(money("50.00"))
         .build().unwrap();
 
         let expected_transaction = Transaction::builder()
@@ -377,7 +458,7 @@ mod tests {
         .set_vendor("Sam Hill Credit Union")
         .set_address("Sam Hill Credit Union")
         .set_category("Opening Balance")
-        .set_amount(500.0)
+        .set_amount(money("500.00"))
         .set_memo("Open Account")
         .set_status("*")
         .add_split(initial_expected_split)
@@ -385,7 +466,7 @@ mod tests {
         .build().unwrap();
 
         let text = format!("D{}\r\nT{:.2}\r\nC{}\r\nN{}\r\nP{}\r\nM{}\r\nA{}\r\nL{}\r\nS{}\r\nE{}\r\n${}\r\nS{}\r\nE{}\r\n${}\r\n^",
-        today.format(format.chrono_str()).to_string(),
+        today.format(format.chrono_str()),
         500.0,
         "*",
         1260,
@@ -400,7 +481,7 @@ mod tests {
         "Bonus for new Account",
         50.0);
 
-        if let Ok(transaction) = Transaction::from_str(&text, &format) {
+        if let Ok(transaction) = Transaction::from_str(&text, &format, &NumberFormat::default()) {
             assert_eq!(transaction, expected_transaction)
         }
 
@@ -412,7 +493,7 @@ mod tests {
         let format = DateFormat::MonthDayFullYear;
 
         let expected = format!("D{}\r\nT{:.2}\r\nC{}\r\nN{}\r\nP{}\r\nM{}\r\nA{}\r\nL{}\r\nS{}\r\nE{}\r\n${:.2}\r\nS{}\r\nE{}\r\n${:.2}\r\n^",
-        today.format(format.chrono_str()).to_string(),
+        today.format(format.chrono_str()),
         500.0,
         "*",
         1260,
@@ -430,13 +511,13 @@ mod tests {
         let initial_split = Split::builder()
         .set_category("Opening Balance")
         .set_memo("Initial Deposit")
-        .set_amount(450.0)
+        .set_amount(money("450.00"))
         .build().unwrap();
 
         let bonus_split = Split::builder()
         .set_category("Opening Balance")
         .set_memo("Bonus for new Account")
-        .set_amount(50.0)
+        .set_amount(money("50.00"))
         .build().unwrap();
 
         let transaction = Transaction::builder()
@@ -445,14 +526,14 @@ mod tests {
         .set_vendor("Sam Hill Credit Union")
         .set_address("Sam Hill Credit Union")
         .set_category("Opening Balance")
-        .set_amount(500.0)
+        .set_amount(money("500.00"))
         .set_memo("Open Account")
         .set_status("*")
         .add_split(initial_split)
         .add_split(bonus_split)
         .build().unwrap();
 
-        assert_eq!(expected, transaction.to_string(&format))
+        assert_eq!(expected, transaction.to_string(&format, &NumberFormat::default()))
     }
 
     #[test]
@@ -462,7 +543,7 @@ mod tests {
 
         let text = format!("!Type:{}\r\nD{}\r\nT{:.2}\r\nC{}\r\nN{}\r\nP{}\r\nM{}\r\nA{}\r\nL{}\r\n^\r\n",
         "Bank",
-        today.format(format.chrono_str()).to_string(),
+        today.format(format.chrono_str()),
         500.0,
         "*",
         1260,
@@ -477,7 +558,7 @@ mod tests {
         .set_vendor("Sam Hill Credit Union")
         .set_address("Sam Hill Credit Union")
         .set_category("Opening Balance")
-        .set_amount(500.0)
+        .set_amount(money("500.00"))
         .set_memo("Open Account")
         .set_status("*")
         .build().unwrap();
@@ -487,7 +568,7 @@ mod tests {
             .add_transaction(expected_transaction)
             .build().unwrap();
 
-        let section = Section::from_str(&text, &format).unwrap();
+        let section = Section::from_str(&text, &format, &NumberFormat::default()).unwrap();
             
         assert_eq!(expected_section, section)
     }
@@ -499,7 +580,7 @@ mod tests {
 
         let expected_text = format!("!Type:{}\r\nD{}\r\nT{:.2}\r\nC{}\r\nN{}\r\nP{}\r\nM{}\r\nA{}\r\nL{}\r\n^\r\n\r\n",
         "Bank",
-        today.format(format.chrono_str()).to_string(),
+        today.format(format.chrono_str()),
         500.0,
         "*",
         1260,
@@ -514,7 +595,7 @@ mod tests {
         .set_vendor("Sam Hill Credit Union")
         .set_address("Sam Hill Credit Union")
         .set_category("Opening Balance")
-        .set_amount(500.0)
+        .set_amount(money("500.00"))
         .set_memo("Open Account")
         .set_status("*")
         .build() {
@@ -522,7 +603,7 @@ mod tests {
             .set_type("Bank")
             .add_transaction(transaction)
             .build() {
-                assert_eq!(expected_text, section.to_string(&format))
+                assert_eq!(expected_text, section.to_string(&format, &NumberFormat::default()))
             }
         }
     }
@@ -538,7 +619,7 @@ mod tests {
         .set_vendor("Sam Hill Credit Union")
         .set_address("Sam Hill Credit Union")
         .set_category("Opening Balance")
-        .set_amount(500.0)
+        .set_amount(money("500.00"))
         .set_memo("Open Account")
         .set_status("*")
         .build().unwrap();
@@ -546,15 +627,17 @@ mod tests {
         let fake_street = Transaction::builder()
         .set_date(&today.format(format.chrono_str()).to_string(), &format)
         .set_vendor("Fake Street Electronics")
+        .set_address("Fake Street Electronics")
         .set_category("Gifts")
-        .set_amount(-200.0)
+        .set_amount(money("-200.00"))
         .set_memo("Headset")
         .build().unwrap();
 
         let velociraptor_entertainment = Transaction::builder()
         .set_date(&today.format(format.chrono_str()).to_string(), &format)
         .set_vendor("Velociraptor Entertainent")
-        .set_amount(50000.0)
+        .set_address("Velociraptor Entertainent")
+        .set_amount(money("50000.00"))
         .set_memo("Pay Day")
         .build().unwrap();
 
@@ -567,7 +650,7 @@ mod tests {
 
         let text = format!("!Type:{}\r\nD{}\r\nT{:.2}\r\nC{}\r\nN{}\r\nP{}\r\nM{}\r\nA{}\r\nL{}\r\n^\r\n\r\nD{}\r\nT{:.2}\r\nC{}\r\nN{}\r\nP{}\r\nM{}\r\nA{}\r\nL{}\r\n^\r\n\r\nD{}\r\nT{:.2}\r\nC{}\r\nN{}\r\nP{}\r\nM{}\r\nA{}\r\nL{}\r\n^\r\n\r\n",
         "Bank",
-        today.format(format.chrono_str()).to_string(),
+        today.format(format.chrono_str()),
         500.0,
         "*",
         1260,
@@ -575,7 +658,7 @@ mod tests {
         "Open Account",
         "Sam Hill Credit Union",
         "Opening Balance",
-        today.format(format.chrono_str()).to_string(),
+        today.format(format.chrono_str()),
         -200.0,
         "",
         0,
@@ -583,7 +666,7 @@ mod tests {
         "Headset",
         "Fake Street Electronics",
         "Gifts",
-        today.format(format.chrono_str()).to_string(),
+        today.format(format.chrono_str()),
         50000.0,
         "",
         0,
@@ -596,7 +679,7 @@ mod tests {
         .set_field(expected_section)
         .build();
 
-        let qif = QIF::from_str(&text, &format);
+        let qif = QIF::from_str(&text, &format, &NumberFormat::default()).unwrap();
 
         assert_eq!(qif, expected_qif)
     }
@@ -612,7 +695,7 @@ mod tests {
         .set_vendor("Sam Hill Credit Union")
         .set_address("Sam Hill Credit Union")
         .set_category("Opening Balance")
-        .set_amount(500.0)
+        .set_amount(money("500.00"))
         .set_memo("Open Account")
         .set_status("*")
         .build().unwrap();
@@ -620,22 +703,23 @@ mod tests {
         let fake_street = Transaction::builder()
         .set_date(&today.format(format.chrono_str()).to_string(), &format)
         .set_vendor("Fake Street Electronics")
+        .set_address("Fake Street Electronics")
         .set_category("Gifts")
-        .set_amount(-200.0)
+        .set_amount(money("-200.00"))
         .set_memo("Headset")
         .build().unwrap();
 
         let velociraptor_entertainment = Transaction::builder()
         .set_date(&today.format(format.chrono_str()).to_string(), &format)
         .set_vendor("Velociraptor Entertainent")
-        .set_amount(50000.0)
+        .set_address("Velociraptor Entertainent")
+        .set_amount(money("50000.00"))
         .set_memo("Pay Day")
         .build().unwrap();
 
         let expected_bank_section = Section::builder()
         .set_type("Bank")
         .add_transaction(sam_hill)
-        .add_transaction(velociraptor_entertainment)
         .build().unwrap();
 
         let expected_credit_card_section = Section::builder()
@@ -643,9 +727,14 @@ mod tests {
         .add_transaction(fake_street)
         .build().unwrap();
 
+        let expected_second_bank_section = Section::builder()
+        .set_type("Bank")
+        .add_transaction(velociraptor_entertainment)
+        .build().unwrap();
+
         let text = format!("!Type:{}\r\nD{}\r\nT{:.2}\r\nC{}\r\nN{}\r\nP{}\r\nM{}\r\nA{}\r\nL{}\r\n^\r\n\r\n!Type:{}\r\nD{}\r\nT{:.2}\r\nC{}\r\nN{}\r\nP{}\r\nM{}\r\nA{}\r\nL{}\r\n^\r\n\r\n!Type:{}\r\nD{}\r\nT{:.2}\r\nC{}\r\nN{}\r\nP{}\r\nM{}\r\nA{}\r\nL{}\r\n^\r\n\r\n",
         "Bank",
-        today.format(format.chrono_str()).to_string(),
+        today.format(format.chrono_str()),
         500.0,
         "*",
         1260,
@@ -654,7 +743,7 @@ mod tests {
         "Sam Hill Credit Union",
         "Opening Balance",
         "CCard",
-        today.format(format.chrono_str()).to_string(),
+        today.format(format.chrono_str()),
         -200.0,
         "",
         0,
@@ -663,7 +752,7 @@ mod tests {
         "Fake Street Electronics",
         "Gifts",
         "Bank",
-        today.format(format.chrono_str()).to_string(),
+        today.format(format.chrono_str()),
         50000.0,
         "",
         0,
@@ -673,11 +762,12 @@ mod tests {
         "");
 
         let expected_qif = QIF::builder()
-        .set_field(expected_bank_section)
-        .set_field(expected_credit_card_section)
+        .add_section(expected_bank_section)
+        .add_section(expected_credit_card_section)
+        .add_section(expected_second_bank_section)
         .build();
 
-        let qif = QIF::from_str(&text, &format);
+        let qif = QIF::from_str(&text, &format, &NumberFormat::default()).unwrap();
 
         assert_eq!(qif, expected_qif)
     }
@@ -693,7 +783,7 @@ mod tests {
         .set_vendor("Sam Hill Credit Union")
         .set_address("Sam Hill Credit Union")
         .set_category("Opening Balance")
-        .set_amount(500.0)
+        .set_amount(money("500.00"))
         .set_memo("Open Account")
         .set_status("*")
         .build().unwrap();
@@ -701,15 +791,17 @@ mod tests {
         let fake_street = Transaction::builder()
         .set_date(&today.format(format.chrono_str()).to_string(), &format)
         .set_vendor("Fake Street Electronics")
+        .set_address("Fake Street Electronics")
         .set_category("Gifts")
-        .set_amount(-200.0)
+        .set_amount(money("-200.00"))
         .set_memo("Headset")
         .build().unwrap();
 
         let velociraptor_entertainment = Transaction::builder()
         .set_date(&today.format(format.chrono_str()).to_string(), &format)
         .set_vendor("Velociraptor Entertainent")
-        .set_amount(50000.0)
+        .set_address("Velociraptor Entertainent")
+        .set_amount(money("50000.00"))
         .set_memo("Pay Day")
         .build().unwrap();
 
@@ -724,6 +816,1453 @@ mod tests {
         .set_field(bank_section)
         .build();
 
-        assert!(qif.save("test.qif", &format).is_ok())
+        assert!(qif.save("test.qif", &format, &NumberFormat::default()).is_ok())
+    }
+
+    #[test]
+    fn investment_action_parses_correctly() {
+        assert_eq!(InvestmentAction::from("ReinvDiv").unwrap(), InvestmentAction::ReinvestDividend);
+        assert_eq!(InvestmentAction::from("Vest").unwrap(), InvestmentAction::Other("Vest".to_string()));
+        assert!(InvestmentAction::from("").is_none());
+    }
+
+    #[test]
+    fn investment_transaction_creation_fails_without_action() {
+        let today = Local::now();
+        let format = DateFormat::MonthDayFullYear;
+
+        let transaction = InvestmentTransaction::builder()
+        .set_date(&today.format(format.chrono_str()).to_string(), &format)
+        .set_security("Velociraptor Entertainment")
+        .build();
+
+        assert_eq!(transaction, Err(TransactionBuildingError::NoAction))
+    }
+
+    #[test]
+    fn parse_investment_transaction_from_string() {
+        let today = Local::now();
+        let format = DateFormat::MonthDayFullYear;
+
+        let expected_transaction = InvestmentTransaction::builder()
+        .set_date(&today.format(format.chrono_str()).to_string(), &format)
+        .set_action("BuyX")
+        .set_security("Velociraptor Entertainment")
+        .set_price(money("25.5"))
+        .set_quantity(money("10"))
+        .set_amount(money("259.95"))
+        .set_commission(money("4.95"))
+        .set_memo("Initial purchase")
+        .set_transfer_account("[Sam Hill Credit Union]")
+        .set_transfer_amount(money("259.95"))
+        .build().unwrap();
+
+        let text = format!("D{}\r\nNBuyX\r\nYVelociraptor Entertainment\r\nI25.5\r\nQ10\r\nT259.95\r\nMInitial purchase\r\nO4.95\r\nL[Sam Hill Credit Union]\r\n$259.95\r\n^",
+        today.format(format.chrono_str()));
+
+        assert_eq!(InvestmentTransaction::from_str(&text, &format, &NumberFormat::default()).unwrap(), expected_transaction);
+        assert_eq!(expected_transaction.to_string(&format, &NumberFormat::default()), text)
+    }
+
+    #[test]
+    fn qif_with_investment_section_round_trips() {
+        let today = Local::now();
+        let format = DateFormat::MonthDayFullYear;
+
+        let dividend = InvestmentTransaction::builder()
+        .set_date(&today.format(format.chrono_str()).to_string(), &format)
+        .set_action("Div")
+        .set_security("Velociraptor Entertainment")
+        .set_amount(money("12.50"))
+        .build().unwrap();
+
+        let reinvestment = InvestmentTransaction::builder()
+        .set_date(&today.format(format.chrono_str()).to_string(), &format)
+        .set_action("ReinvDiv")
+        .set_security("Velociraptor Entertainment")
+        .set_price(money("25.0"))
+        .set_quantity(money("0.5"))
+        .set_amount(money("12.50"))
+        .build().unwrap();
+
+        let bank_transaction = Transaction::builder()
+        .set_date(&today.format(format.chrono_str()).to_string(), &format)
+        .set_vendor("Sam Hill Credit Union")
+        .set_amount(money("500.00"))
+        .build().unwrap();
+
+        let investment_section = Section::builder()
+        .set_type("Invst")
+        .add_investment_transaction(dividend)
+        .add_investment_transaction(reinvestment)
+        .build().unwrap();
+
+        let bank_section = Section::builder()
+        .set_type("Bank")
+        .add_transaction(bank_transaction)
+        .build().unwrap();
+
+        let qif = QIF::builder()
+        .set_field(bank_section)
+        .set_field(investment_section)
+        .build();
+
+        assert_eq!(QIF::from_str(&qif.to_string(&format, &NumberFormat::default()), &format, &NumberFormat::default()).unwrap(), qif)
+    }
+
+    #[test]
+    fn memorized_transaction_type_parses_correctly() {
+        assert_eq!(MemorizedTransactionType::from("E").unwrap(), MemorizedTransactionType::Electronic);
+        assert!(MemorizedTransactionType::from("Z").is_none());
+    }
+
+    #[test]
+    fn parse_memorized_transaction_with_amortization() {
+        let today = Local::now();
+        let format = DateFormat::MonthDayFullYear;
+
+        let split = Split::builder()
+        .set_category("Mortgage Interest")
+        .set_amount(money("-400.00"))
+        .build().unwrap();
+
+        let amortization = Amortization {
+            first_payment_date: Transaction::builder()
+            .set_date(&today.format(format.chrono_str()).to_string(), &format)
+            .date,
+            total_years: Some(30),
+            payments_made: Some(12),
+            periods_per_year: Some(12),
+            interest_rate: Some(6.5),
+            current_balance: Some(money("148000.00")),
+            original_amount: Some(money("150000.00"))
+        };
+
+        let expected = MemorizedTransaction::builder()
+        .set_type("P")
+        .set_vendor("Sam Hill Credit Union")
+        .set_category("Mortgage")
+        .set_amount(money("-948.10"))
+        .add_split(split)
+        .set_amortization(amortization)
+        .build().unwrap();
+
+        let text = format!("KP\r\nT-948.10\r\nPSam Hill Credit Union\r\nLMortgage\r\nSMortgage Interest\r\n$-400.00\r\n1{}\r\n230\r\n312\r\n412\r\n56.5\r\n6148000.00\r\n7150000.00\r\n^",
+        today.format(format.chrono_str()));
+
+        let memorized_transaction = MemorizedTransaction::from_str(&text, &format, &NumberFormat::default()).unwrap();
+
+        assert_eq!(memorized_transaction, expected);
+        assert_eq!(MemorizedTransaction::from_str(&memorized_transaction.to_string(&format, &NumberFormat::default()), &format, &NumberFormat::default()).unwrap(), expected)
+    }
+
+    #[test]
+    fn memorized_transaction_creation_fails_without_type() {
+        let transaction = MemorizedTransaction::builder()
+        .set_vendor("Sam Hill Credit Union")
+        .set_amount(money("500.00"))
+        .build();
+
+        assert_eq!(transaction, Err(TransactionBuildingError::NoMemorizedType))
+    }
+
+    #[test]
+    fn parse_qif_with_memorized_section() {
+        let format = DateFormat::MonthDayFullYear;
+
+        let paycheck = MemorizedTransaction::builder()
+        .set_type("D")
+        .set_vendor("Velociraptor Entertainent")
+        .set_amount(money("50000.00"))
+        .set_memo("Pay Day")
+        .build().unwrap();
+
+        let headset = MemorizedTransaction::builder()
+        .set_type("C")
+        .set_vendor("Fake Street Electronics")
+        .set_category("Gifts")
+        .set_amount(money("-200.00"))
+        .build().unwrap();
+
+        let text = "!Type:Memorized\r\nKD\r\nT50000.00\r\nPVelociraptor Entertainent\r\nMPay Day\r\n^\r\nKC\r\nT-200.00\r\nPFake Street Electronics\r\nLGifts\r\n^\r\n";
+
+        let expected_qif = QIF::builder()
+        .set_field(Section::builder()
+            .set_type("Memorized")
+            .add_memorized_transaction(paycheck)
+            .add_memorized_transaction(headset)
+            .build().unwrap())
+        .build();
+
+        assert_eq!(QIF::from_str(text, &format, &NumberFormat::default()).unwrap(), expected_qif)
+    }
+
+    #[test]
+    fn parse_category_from_string() {
+        let expected = Category::builder()
+        .set_name("Salary")
+        .set_description("Pay from work")
+        .set_tax_related(true)
+        .set_income(true)
+        .set_tax_schedule("460")
+        .build().unwrap();
+
+        let text = "NSalary\r\nDPay from work\r\nT\r\nI\r\nR460\r\n^";
+
+        assert_eq!(Category::from_str(text).unwrap(), expected);
+        assert_eq!(expected.to_string(&NumberFormat::default()), text)
+    }
+
+    #[test]
+    fn category_creation_fails_without_name() {
+        let category = Category::builder()
+        .set_description("Pay from work")
+        .build();
+
+        assert!(category.is_none())
+    }
+
+    #[test]
+    fn qif_with_categories_writes_them_before_sections() {
+        let today = Local::now();
+        let format = DateFormat::MonthDayFullYear;
+
+        let gifts = Category::builder()
+        .set_name("Gifts")
+        .set_expense(true)
+        .set_budget_amount(money("250.00"))
+        .build().unwrap();
+
+        let salary = Category::builder()
+        .set_name("Salary")
+        .set_income(true)
+        .build().unwrap();
+
+        let fake_street = Transaction::builder()
+        .set_date(&today.format(format.chrono_str()).to_string(), &format)
+        .set_vendor("Fake Street Electronics")
+        .set_category("Gifts")
+        .set_amount(money("-200.00"))
+        .build().unwrap();
+
+        let qif = QIF::builder()
+        .add_category(gifts)
+        .add_category(salary)
+        .add_section(Section::builder()
+            .set_type("Bank")
+            .add_transaction(fake_street)
+            .build().unwrap())
+        .build();
+
+        let text = qif.to_string(&format, &NumberFormat::default());
+
+        assert!(text.starts_with("!Type:Cat\r\nNGifts\r\nE\r\nB250.00\r\n^\r\n\r\nNSalary\r\nI\r\n^\r\n\r\n!Type:Bank\r\n"));
+        assert_eq!(QIF::from_str(&text, &format, &NumberFormat::default()).unwrap(), qif)
+    }
+
+    #[test]
+    fn transaction_category_separates_class() {
+        let today = Local::now();
+        let format = DateFormat::MonthDayFullYear;
+
+        let text = format!("D{}\r\nT-45.00\r\nPFake Street Gas\r\nLAuto:Fuel/Business\r\nSAuto:Fuel/Business\r\n$-40.00\r\nS/Personal\r\n$-5.00\r\n^",
+        today.format(format.chrono_str()));
+
+        let transaction = Transaction::from_str(&text, &format, &NumberFormat::default()).unwrap();
+
+        assert_eq!(transaction.category_name().as_deref(), Some("Auto:Fuel"));
+        assert_eq!(transaction.class_name(), Some("Business"));
+        assert_eq!(transaction.splits[1].category_name(), None);
+        assert_eq!(transaction.splits[1].class_name(), Some("Personal"));
+        assert!(transaction.to_string(&format, &NumberFormat::default()).contains("LAuto:Fuel/Business\r\n"))
+    }
+
+    #[test]
+    fn parse_qif_with_class_list() {
+        let format = DateFormat::MonthDayFullYear;
+
+        let business = Class::builder()
+        .set_name("Business")
+        .set_description("Side business")
+        .build().unwrap();
+
+        let personal = Class::builder()
+        .set_name("Personal")
+        .build().unwrap();
+
+        let text = "!Type:Class\r\nNBusiness\r\nDSide business\r\n^\r\nNPersonal\r\n^\r\n";
+
+        let qif = QIF::from_str(text, &format, &NumberFormat::default()).unwrap();
+
+        assert_eq!(qif.classes().collect::<Vec<&Class>>(), vec![&business, &personal]);
+        assert_eq!(QIF::from_str(&qif.to_string(&format, &NumberFormat::default()), &format, &NumberFormat::default()).unwrap(), qif)
+    }
+
+    #[test]
+    fn parse_account_from_string() {
+        let today = Local::now();
+        let format = DateFormat::MonthDayFullYear;
+
+        let expected = Account::builder()
+        .set_name("Visa")
+        .set_type("CCard")
+        .set_description("Sam Hill Credit Union Visa")
+        .set_credit_limit(money("5000.00"))
+        .set_statement_balance_date(&today.format(format.chrono_str()).to_string(), &format)
+        .set_statement_balance(money("-250.00"))
+        .build().unwrap();
+
+        let text = format!("NVisa\r\nTCCard\r\nDSam Hill Credit Union Visa\r\nL5000.00\r\n/{}\r\n$-250.00\r\n^",
+        today.format(format.chrono_str()));
+
+        assert_eq!(Account::from_str(&text, &format, &NumberFormat::default()).unwrap(), expected);
+        assert_eq!(expected.to_string(&format, &NumberFormat::default()), text)
+    }
+
+    #[test]
+    fn parse_qif_with_multiple_accounts_of_the_same_type() {
+        let today = Local::now();
+        let format = DateFormat::MonthDayFullYear;
+
+        let text = format!("!Option:AutoSwitch\r\n!Account\r\nNChecking\r\nTBank\r\n^\r\nNSavings\r\nTBank\r\n^\r\n!Clear:AutoSwitch\r\n!Account\r\nNChecking\r\nTBank\r\n^\r\n!Type:Bank\r\nD{0}\r\nT-200.00\r\nPFake Street Electronics\r\n^\r\nD{0}\r\nT50000.00\r\nPVelociraptor Entertainent\r\n^\r\n!Account\r\nNSavings\r\nTBank\r\n^\r\n!Type:Bank\r\nD{0}\r\nT500.00\r\nPSam Hill Credit Union\r\n^\r\n",
+        today.format(format.chrono_str()));
+
+        let qif = QIF::from_str(&text, &format, &NumberFormat::default()).unwrap();
+
+        assert_eq!(qif.accounts().map(|account| account.name.as_str()).collect::<Vec<&str>>(), vec!["Checking", "Savings"]);
+        assert!(qif.section(Type::Bank).is_none());
+        assert_eq!(qif.account_sections().count(), 2);
+        assert_eq!(qif.account_section("Checking").unwrap().transactions.len(), 2);
+        assert_eq!(qif.account_section("Savings").unwrap().transactions[0].vendor, "Sam Hill Credit Union");
+        assert_eq!(QIF::from_str(&qif.to_string(&format, &NumberFormat::default()), &format, &NumberFormat::default()).unwrap(), qif)
+    }
+
+    #[test]
+    fn parse_security_from_string() {
+        let expected = Security::builder()
+        .set_name("Velociraptor Entertainment")
+        .set_symbol("VLCR")
+        .set_type("Stock")
+        .set_goal("Growth")
+        .build().unwrap();
+
+        let text = "NVelociraptor Entertainment\r\nSVLCR\r\nTStock\r\nGGrowth\r\n^";
+
+        assert_eq!(Security::from_str(text).unwrap(), expected);
+        assert_eq!(expected.to_string(), text)
+    }
+
+    #[test]
+    fn price_quote_uses_date_format() {
+        let format = DateFormat::FullYearMonthDay;
+
+        let quote = PriceQuote::from_str("\"VLCR\",25.50,\"2024-01-05\"", &format, &NumberFormat::default()).unwrap();
+
+        assert_eq!(quote.symbol, "VLCR");
+        assert_eq!(quote.price, money("25.5"));
+        assert_eq!(quote.to_string(&format, &NumberFormat::default()), "\"VLCR\",25.50,\"2024-01-05\"");
+        assert!(PriceQuote::from_str("\"VLCR\",25.5,\"01/05/2024\"", &format, &NumberFormat::default()).is_err())
+    }
+
+    #[test]
+    fn price_quote_reads_prices_with_separators() {
+        let format = DateFormat::FullYearMonthDay;
+        let european = NumberFormat::from("1.234,56").unwrap();
+
+        let quote = PriceQuote::from_str("\"VLCR\",1,234.50,\"2024-01-05\"", &format, &NumberFormat::default()).unwrap();
+        let european_quote = PriceQuote::from_str("\"VLCR\",\"1.234,50\",\"2024-01-05\"", &format, &european).unwrap();
+
+        assert_eq!(quote.price, money("1234.50"));
+        assert_eq!(european_quote.price, money("1234.50"));
+        assert_eq!(european_quote.to_string(&format, &european), "\"VLCR\",\"1234,50\",\"2024-01-05\"");
+        assert!(matches!(PriceQuote::from_str("\"VLCR\",,\"2024-01-05\"", &format, &NumberFormat::default()), Err(QifError::MissingField { field: 'P', .. })));
+        assert!(matches!(PriceQuote::from_str("\"VLCR\",NaN,\"2024-01-05\"", &format, &NumberFormat::default()), Err(QifError::BadAmount { .. })))
+    }
+
+    #[test]
+    fn parse_qif_with_securities_and_prices() {
+        let format = DateFormat::MonthDayFullYear;
+
+        let text = "!Type:Security\r\nNVelociraptor Entertainment\r\nSVLCR\r\nTStock\r\n^\r\nNFake Street Bond Fund\r\nSFSBF\r\nTBond\r\n^\r\n!Type:Prices\r\n\"VLCR\",25.5,\"01/05/2024\"\r\n^\r\n\"FSBF\",10,\"01/05/2024\"\r\n^\r\n";
+
+        let qif = QIF::from_str(text, &format, &NumberFormat::default()).unwrap();
+
+        assert_eq!(qif.securities().count(), 2);
+        assert_eq!(qif.securities().nth(1).unwrap().symbol, Some("FSBF".to_string()));
+        assert_eq!(qif.prices().count(), 2);
+        assert_eq!(qif.prices().next().unwrap().price, money("25.5"));
+        assert_eq!(QIF::from_str(&qif.to_string(&format, &NumberFormat::default()), &format, &NumberFormat::default()).unwrap(), qif)
+    }
+
+    #[test]
+    fn parse_invoice_with_line_items() {
+        let today = Local::now();
+        let format = DateFormat::MonthDayFullYear;
+
+        let consultation = LineItem::builder()
+        .set_description("Consultation")
+        .set_category("Services")
+        .set_quantity(money("2"))
+        .set_price(money("75.00"))
+        .set_taxable(true)
+        .build().unwrap();
+
+        let travel = LineItem::builder()
+        .set_description("Travel")
+        .set_price(money("12.00"))
+        .build().unwrap();
+
+        let expected = Invoice::builder()
+        .set_date(&today.format(format.chrono_str()).to_string(), &format)
+        .set_number(1001)
+        .set_customer("Velociraptor Entertainment")
+        .set_category("Accounts Receivable")
+        .set_amount(money("162.00"))
+        .set_type("1")
+        .set_due_date(&today.format(format.chrono_str()).to_string(), &format)
+        .set_tax_rate(8.25)
+        .add_line_item(consultation)
+        .add_line_item(travel)
+        .build().unwrap();
+
+        let text = format!("D{0}\r\nT162.00\r\nN1001\r\nPVelociraptor Entertainment\r\nLAccounts Receivable\r\nXI1\r\nXE{0}\r\nXR8.25\r\nXSConsultation\r\nXNServices\r\nX#2\r\nX$75.00\r\nXF\r\nXSTravel\r\nX$12.00\r\n^",
+        today.format(format.chrono_str()));
+
+        let invoice = Invoice::from_str(&text, &format, &NumberFormat::default()).unwrap();
+
+        assert_eq!(invoice, expected);
+        assert!(invoice.splits.is_empty());
+        assert_eq!(invoice.line_items[0].amount(RoundingMode::HalfEven), Some(money("150.00")));
+        assert_eq!(Invoice::from_str(&invoice.to_string(&format, &NumberFormat::default()), &format, &NumberFormat::default()).unwrap(), expected)
+    }
+
+    #[test]
+    fn qif_with_invoice_section_round_trips() {
+        let today = Local::now();
+        let format = DateFormat::MonthDayFullYear;
+
+        let invoice = Invoice::builder()
+        .set_date(&today.format(format.chrono_str()).to_string(), &format)
+        .set_customer("Velociraptor Entertainment")
+        .set_amount(money("75.00"))
+        .add_line_item(LineItem::builder()
+            .set_description("Consultation")
+            .set_price(money("75.00"))
+            .build().unwrap())
+        .build().unwrap();
+
+        let qif = QIF::builder()
+        .set_field(Section::builder()
+            .set_type("Invoice")
+            .add_invoice(invoice)
+            .build().unwrap())
+        .build();
+
+        assert_eq!(QIF::from_str(&qif.to_string(&format, &NumberFormat::default()), &format, &NumberFormat::default()).unwrap(), qif)
+    }
+
+    #[test]
+    fn check_number_parses_named_references() {
+        assert_eq!(CheckNumber::from("1260").unwrap(), CheckNumber::Number(1260));
+        assert_eq!(CheckNumber::from("ATM").unwrap(), CheckNumber::Atm);
+        assert_eq!(CheckNumber::from("TXFR").unwrap(), CheckNumber::Transfer);
+        assert_eq!(CheckNumber::from("0012").unwrap(), CheckNumber::Text("0012".to_string()));
+        assert_eq!(CheckNumber::from("Online").unwrap(), CheckNumber::Text("Online".to_string()));
+        assert!(CheckNumber::from("").is_none())
+    }
+
+    #[test]
+    fn non_numeric_check_numbers_survive_round_trip() {
+        let today = Local::now();
+        let format = DateFormat::MonthDayFullYear;
+
+        for reference in ["ATM", "EFT", "DEP", "TXFR", "Print", "0012", "Web Payment"] {
+            let text = format!("D{}\r\nT-20.00\r\nC\r\nN{}\r\nPFake Street Electronics\r\nM\r\nAFake Street Electronics\r\nL\r\n^",
+            today.format(format.chrono_str()),
+            reference);
+
+            let transaction = Transaction::from_str(&text, &format, &NumberFormat::default()).unwrap();
+
+            assert_eq!(transaction.check_number.as_ref().unwrap().to_string(), reference);
+            assert_eq!(transaction.to_string(&format, &NumberFormat::default()), text)
+        }
+    }
+
+    #[test]
+    fn category_ref_parses_transfers_and_subcategories() {
+        assert_eq!(CategoryRef::from("[Savings]/Business").unwrap(), CategoryRef::Transfer { account: "Savings".to_string(), class: Some("Business".to_string()) });
+        assert_eq!(CategoryRef::from("Auto:Fuel").unwrap(), CategoryRef::Subcategory { path: vec!["Auto".to_string(), "Fuel".to_string()], class: None });
+        assert_eq!(CategoryRef::from("Gifts").unwrap(), CategoryRef::Category { name: "Gifts".to_string(), class: None });
+        assert_eq!(CategoryRef::from("[Savings/Joint]/Business").unwrap(), CategoryRef::Transfer { account: "Savings/Joint".to_string(), class: Some("Business".to_string()) });
+        assert_eq!(CategoryRef::from("[Savings/Joint]").unwrap().transfer_account(), Some("Savings/Joint"));
+        assert!(!CategoryRef::from("[Savings]Extra").unwrap().is_transfer());
+        assert!(CategoryRef::from("").is_none());
+
+        for text in ["[Savings]/Business", "[Savings/Joint]", "Auto:Fuel", "Gifts", "/Personal", "Food/"] {
+            assert_eq!(CategoryRef::from(text).unwrap().to_string(), text)
+        }
+    }
+
+    #[test]
+    fn transaction_recognizes_transfers() {
+        let today = Local::now();
+        let format = DateFormat::MonthDayFullYear;
+
+        let text = format!("D{}\r\nT-100.00\r\nPSam Hill Credit Union\r\nL[Savings]\r\nS[Savings]\r\n$-60.00\r\nSGifts\r\n$-40.00\r\n^",
+        today.format(format.chrono_str()));
+
+        let transaction = Transaction::from_str(&text, &format, &NumberFormat::default()).unwrap();
+
+        assert!(transaction.is_transfer());
+        assert_eq!(transaction.transfer_account(), Some("Savings"));
+        assert_eq!(transaction.category_name(), None);
+        assert!(transaction.splits[0].is_transfer());
+        assert!(!transaction.splits[1].is_transfer());
+        assert_eq!(transaction.splits[1].category_name().as_deref(), Some("Gifts"))
+    }
+
+    #[test]
+    fn multi_line_addresses_survive_round_trip() {
+        let today = Local::now();
+        let format = DateFormat::MonthDayFullYear;
+
+        let text = format!("D{}\r\nT-950.00\r\nC\r\nN1261\r\nPFake Street Apartments\r\nM\r\nAFake Street Apartments\r\nA123 Fake Street\r\nASpringfield\r\nLRent\r\n^",
+        today.format(format.chrono_str()));
+
+        let transaction = Transaction::from_str(&text, &format, &NumberFormat::default()).unwrap();
+
+        assert_eq!(transaction.address, vec!["Fake Street Apartments", "123 Fake Street", "Springfield"]);
+        assert_eq!(transaction.to_string(&format, &NumberFormat::default()), text)
+    }
+
+    #[test]
+    fn vendor_as_address_is_an_explicit_option() {
+        let today = Local::now().format("%m/%d/%Y").to_string();
+
+        let with_fallback = Transaction::builder()
+        .set_date(&today, &DateFormat::MonthDayFullYear)
+        .set_vendor("Fake Street Apartments")
+        .set_vendor_as_address(true)
+        .set_amount(money("-950.00"))
+        .build().unwrap();
+
+        let without_fallback = Transaction::builder()
+        .set_date(&today, &DateFormat::MonthDayFullYear)
+        .set_vendor("Fake Street Apartments")
+        .set_amount(money("-950.00"))
+        .build().unwrap();
+
+        assert_eq!(with_fallback.address, vec!["Fake Street Apartments"]);
+        assert!(without_fallback.address.is_empty())
+    }
+
+    #[test]
+    fn unknown_transaction_fields_survive_round_trip() {
+        let today = Local::now();
+        let format = DateFormat::MonthDayFullYear;
+
+        let text = format!("D{}\r\nT-42.50\r\nC\r\nN\r\nPFake Street Electronics\r\nM\r\nAFake Street Electronics\r\nLGifts\r\nSGifts\r\nE\r\n$-42.50\r\nF\r\nZvendor extension\r\n^",
+        today.format(format.chrono_str()));
+
+        let transaction = Transaction::from_str(&text, &format, &NumberFormat::default()).unwrap();
+
+        assert_eq!(transaction.unknown_fields, vec!["F", "Zvendor extension"]);
+        assert_eq!(transaction.to_string(&format, &NumberFormat::default()), text)
+    }
+
+    #[test]
+    fn unknown_section_headers_survive_round_trip() {
+        let today = Local::now();
+        let format = DateFormat::MonthDayFullYear;
+
+        let text = format!("!Option:Custom\r\n!Type:Bank\r\nD{}\r\nT-42.50\r\nC\r\nN\r\nPFake Street Electronics\r\nM\r\nAFake Street Electronics\r\nLGifts\r\nF\r\n^\r\n\r\n",
+        today.format(format.chrono_str()));
+
+        let qif = QIF::from_str(&text, &format, &NumberFormat::default()).unwrap();
+
+        assert_eq!(qif.section(Type::Bank).unwrap().unknown_fields, vec!["!Option:Custom"]);
+        assert_eq!(qif.to_string(&format, &NumberFormat::default()), text);
+        assert_eq!(QIF::from_str(&qif.to_string(&format, &NumberFormat::default()), &format, &NumberFormat::default()).unwrap(), qif)
+    }
+
+    #[test]
+    fn number_format_parses_locale_amounts() {
+        let us = NumberFormat::default();
+        let european = NumberFormat::from("1.234,56").unwrap();
+
+        assert_eq!(us.parse("-1,234.56"), Some(money("-1234.56")));
+        assert_eq!(us.parse("$12.00"), Some(money("12.00")));
+        assert_eq!(us.parse("(45.00)"), Some(money("-45.00")));
+        assert_eq!(us.parse("-$1,000"), Some(money("-1000")));
+        assert_eq!(us.parse("twelve"), None);
+        assert_eq!(european.parse("1.234,56"), Some(money("1234.56")));
+        assert_eq!(european.parse("€-12,50"), Some(money("-12.50")));
+        assert_eq!(european.format(money("-1234.5")), "-1234,50")
+    }
+
+    #[test]
+    fn transaction_uses_number_format() {
+        let today = Local::now();
+        let format = DateFormat::MonthDayFullYear;
+        let european = NumberFormat::from("1.234,56").unwrap();
+
+        let text = format!("D{}\r\nT-1.234,56\r\nPFake Street Electronics\r\nSGifts\r\n$(1.000,00)\r\nSFood\r\n%(18,9)\r\n^",
+        today.format(format.chrono_str()));
+
+        let transaction = Transaction::from_str(&text, &format, &european).unwrap();
+
+        assert_eq!(transaction.amount, money("-1234.56"));
+        assert_eq!(transaction.splits[0].amount, money("-1000.00"));
+        assert_eq!(transaction.splits[1].amount, money("233.33"));
+        assert!(transaction.to_string(&format, &european).contains("T-1234,56\r\n"));
+        assert!(transaction.to_string(&format, &european).contains("$-1000,00\r\n"))
+    }
+
+    #[test]
+    fn quicken_and_day_first_dates_are_parsed() {
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day);
+
+        assert_eq!(DateFormat::QuickenApostrophe.parse(" 1/ 5'04"), date(2004, 1, 5));
+        assert_eq!(DateFormat::QuickenApostrophe.parse("12/31'99"), date(1999, 12, 31));
+        assert_eq!(DateFormat::QuickenApostrophe.parse("12/31/1999"), date(1999, 12, 31));
+        assert_eq!(DateFormat::QuickenPadded.parse(" 1/ 5/2004"), date(2004, 1, 5));
+        assert_eq!(DateFormat::DayMonthFullYear.parse("31/12/2004"), date(2004, 12, 31));
+        assert_eq!(DateFormat::DayMonthFullYearDotted.parse("31.12.2004"), date(2004, 12, 31));
+        assert_eq!(DateFormat::Custom("%d %b %Y".to_string()).parse("05 Jan 2004"), date(2004, 1, 5));
+        assert_eq!(DateFormat::MonthDayShortYear.parse("01/05/60"), date(2060, 1, 5));
+        assert_eq!(DateFormat::MonthDayShortYear.with_pivot(50).parse("01/05/60"), date(1960, 1, 5));
+        assert_eq!(DateFormat::MonthDayFullYear.parse("31/12/2004"), None)
+    }
+
+    #[test]
+    fn transaction_with_quicken_date_survives_round_trip() {
+        let format = DateFormat::QuickenApostrophe;
+
+        let text = "D 1/ 5'04\r\nT-20.00\r\nC\r\nN\r\nPFake Street Electronics\r\nM\r\nAFake Street Electronics\r\nL\r\n^";
+
+        let transaction = Transaction::from_str(text, &format, &NumberFormat::default()).unwrap();
+
+        assert_eq!(transaction.date.date_naive(), NaiveDate::from_ymd_opt(2004, 1, 5).unwrap());
+        assert_eq!(transaction.to_string(&format, &NumberFormat::default()), text)
+    }
+
+    #[test]
+    fn date_format_is_detected() {
+        let day_first = "!Type:Cat\r\nNGifts\r\nD12/01/2004\r\n^\r\n!Type:Bank\r\nD05/01/2004\r\nT-20.00\r\nPFake Street Electronics\r\n^\r\nD31/01/2004\r\nT-20.00\r\nPFake Street Electronics\r\n^\r\n";
+        let detection = DateFormat::detect(day_first);
+
+        assert_eq!(detection.format, Some(DateFormat::DayMonthFullYear));
+        assert_eq!(detection.dates_found, 2);
+        assert_eq!(detection.confidence, 1.0);
+        assert!(!detection.is_ambiguous());
+
+        let quicken = "!Type:Bank\r\nD 1/ 5'04\r\nT-20.00\r\nPFake Street Electronics\r\n^\r\n";
+
+        assert_eq!(DateFormat::detect(quicken).format, Some(DateFormat::QuickenApostrophe));
+
+        let unclear = "!Type:Bank\r\nD01/05/2004\r\nT-20.00\r\nPFake Street Electronics\r\n^\r\n";
+        let detection = DateFormat::detect(unclear);
+
+        assert_eq!(detection.format, Some(DateFormat::MonthDayFullYear));
+        assert_eq!(detection.alternatives, vec![DateFormat::DayMonthFullYear]);
+
+        let detection = DateFormat::detect(&format!("!Option:DMY\r\n{}", unclear));
+
+        assert_eq!(detection.format, Some(DateFormat::DayMonthFullYear));
+        assert!(!detection.is_ambiguous())
+    }
+
+    #[test]
+    fn parse_qif_with_detected_date_format() {
+        let text = "!Type:Bank\r\nD31.01.2004\r\nT-1.234,56\r\nPFake Street Electronics\r\n^\r\n";
+
+        let qif = QIF::from_str_auto(text, &NumberFormat::from("1.234,56").unwrap()).unwrap();
+        let transaction = &qif.section(Type::Bank).unwrap().transactions[0];
+
+        assert_eq!(transaction.date.date_naive(), NaiveDate::from_ymd_opt(2004, 1, 31).unwrap());
+        assert_eq!(transaction.amount, money("-1234.56"))
+    }
+
+    #[test]
+    fn headers_are_parsed() {
+        assert_eq!(Header::from("!Type:Bank"), Some(Header::Type(Type::Bank)));
+        assert_eq!(Header::from("!type:bank  "), Some(Header::Type(Type::Bank)));
+        assert_eq!(Header::from("!Type: Oth L"), Some(Header::Type(Type::Liability)));
+        assert_eq!(Header::from("!TYPE:cat"), Some(Header::Category));
+        assert_eq!(Header::from("!account"), Some(Header::Account));
+        assert_eq!(Header::from("!Option:autoswitch"), Some(Header::Option(HeaderOption::AutoSwitch)));
+        assert_eq!(Header::from("!Clear:AutoSwitch"), Some(Header::Clear(HeaderOption::AutoSwitch)));
+        assert_eq!(Header::from("!Option:DMY"), Some(Header::Option(HeaderOption::DayMonthYear)));
+        assert_eq!(Header::from("!Type:Budget"), Some(Header::Unknown("!Type:Budget".to_string())));
+        assert_eq!(Header::from("NChecking"), None);
+        assert_eq!(Header::Type(Type::Asset).to_string(), "!Type:Oth A")
+    }
+
+    #[test]
+    fn parse_qif_with_liability_and_asset_sections() {
+        let text = "!type:oth l \r\nD01/05/2004\r\nT-950.00\r\nPFake Street Apartments\r\n^\r\n!Type:Oth A\r\nD01/05/2004\r\nT2500.00\r\nPVelociraptor Entertainment\r\n^\r\n";
+        let format = DateFormat::MonthDayFullYear;
+
+        let qif = QIF::from_str(text, &format, &NumberFormat::default()).unwrap();
+
+        assert_eq!(qif.section(Type::Liability).unwrap().transactions[0].amount, money("-950.00"));
+        assert_eq!(qif.section(Type::Asset).unwrap().transactions[0].amount, money("2500.00"));
+        assert_eq!(QIF::from_str(&qif.to_string(&format, &NumberFormat::default()), &format, &NumberFormat::default()).unwrap(), qif)
+    }
+
+    #[test]
+    fn parser_finds_headers_and_records() {
+        let text = "!Type:Bank\r\nD01/05/2004\r\nT-20.00\r\n^\r\n\r\nD01/06/2004\r\nT-30.00\r\n!Type:Cat\r\nNGifts\r\n";
+
+        let events: Vec<Event<&str>> = Parser::new(text.lines()).collect();
+
+        assert_eq!(events, vec![
+            Event::Header(1, Header::Type(Type::Bank)),
+            Event::Record(2, vec!["D01/05/2004", "T-20.00"]),
+            Event::Record(6, vec!["D01/06/2004", "T-30.00"]),
+            Event::Header(8, Header::Category),
+            Event::Record(9, vec!["NGifts"])
+        ])
+    }
+
+    #[test]
+    fn caret_in_memo_does_not_end_record() {
+        let text = "!Type:Bank\r\nD01/05/2004\r\nT-20.00\r\nPFake Street Electronics^2\r\nMRef ^ 1260\r\n^\r\nD01/06/2004\r\nT-30.00\r\nPSam Hill Credit Union\r\n^\r\n";
+        let format = DateFormat::MonthDayFullYear;
+
+        let qif = QIF::from_str(text, &format, &NumberFormat::default()).unwrap();
+        let transactions = &qif.section(Type::Bank).unwrap().transactions;
+
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(transactions[0].vendor, "Fake Street Electronics^2");
+        assert_eq!(transactions[0].memo, "Ref ^ 1260");
+        assert_eq!(QIF::from_str(&qif.to_string(&format, &NumberFormat::default()), &format, &NumberFormat::default()).unwrap(), qif)
+    }
+
+    #[test]
+    fn qif_error_gives_line_and_record() {
+        let format = DateFormat::MonthDayFullYear;
+        let text = "!Type:Bank\r\nD01/05/2004\r\nT-20.00\r\nPFake Street Electronics\r\n^\r\nD01/06/2004\r\nTtwelve\r\nPSam Hill Credit Union\r\n^\r\n";
+
+        let error = QIF::from_str(text, &format, &NumberFormat::default()).unwrap_err();
+
+        assert!(matches!(&error, QifError::BadAmount { line: 7, record: 1, text } if text == "Ttwelve"));
+        assert_eq!(error.to_string(), "Amount could not be parsed on line 7 (record 1): Ttwelve");
+
+        let error = QIF::from_str("!Type:Bank\r\nD13/45/2004\r\nT-20.00\r\n^\r\n", &format, &NumberFormat::default()).unwrap_err();
+
+        assert!(matches!(error, QifError::BadDate { line: 2, record: 0, .. }));
+
+        let error = QIF::from_str("!Type:Bank\r\nT-20.00\r\nPFake Street Electronics\r\n^\r\n!Type:Nonsense\r\n", &format, &NumberFormat::default()).unwrap_err();
+
+        assert!(matches!(error, QifError::MissingField { field: 'D', line: 2, record: 0, .. }));
+
+        let error = QIF::from_str("!Type:Nonsense\r\n", &format, &NumberFormat::default()).unwrap_err();
+
+        assert!(matches!(&error, QifError::BadHeader { line: 1, record: 0, text } if text == "!Type:Nonsense"));
+
+        let error = QIF::load_from_file("does/not/exist.qif", &format, &NumberFormat::default()).unwrap_err();
+
+        assert!(matches!(error, QifError::Io(_)));
+        assert!(std::error::Error::source(&error).is_some())
+    }
+
+    #[test]
+    fn split_field_without_category_is_an_error() {
+        let format = DateFormat::MonthDayFullYear;
+        let text = "D01/05/2004\r\nT-20.00\r\nPFake Street Electronics\r\nEBirthday present\r\n$-20.00\r\n^";
+
+        let error = Transaction::from_str(text, &format, &NumberFormat::default()).unwrap_err();
+
+        assert!(matches!(&error, QifError::OrphanSplitField { line: 4, record: 0, text } if text == "EBirthday present"));
+        assert_eq!(error.line(), Some(4));
+        assert_eq!(error.record(), Some(0))
+    }
+
+    #[test]
+    fn lenient_mode_keeps_records_and_collects_warnings() {
+        let format = DateFormat::MonthDayFullYear;
+        let text = "!Type:Bank\r\nD13/45/2004\r\nT-20.00\r\nPFake Street Electronics\r\n^\r\nD01/06/2004\r\nTtwelve\r\nPSam Hill Credit Union\r\n^\r\n!Frobnicate\r\n";
+
+        assert!(matches!(QIF::from_str(text, &format, &NumberFormat::default()), Err(QifError::BadDate { line: 2, .. })));
+
+        let (qif, warnings) = QIF::from_str_with(text, &ParseOptions::lenient(&format, &NumberFormat::default())).unwrap();
+        let bank = qif.section(Type::Bank).unwrap();
+
+        assert_eq!(bank.transactions.len(), 2);
+        assert_eq!(bank.transactions[0].date, DateTime::<Local>::default());
+        assert_eq!(bank.transactions[1].amount, Money::zero());
+        assert_eq!(qif.items.last(), Some(&QifItem::Header(Header::Unknown(String::from("!Frobnicate")))));
+        assert_eq!(warnings.len(), 5);
+        assert!(matches!(warnings[0], QifError::BadDate { line: 2, record: 0, .. }));
+        assert!(matches!(warnings[1], QifError::MissingField { field: 'D', line: 2, record: 0, .. }));
+        assert!(matches!(warnings[2], QifError::BadAmount { line: 7, record: 1, .. }));
+        assert!(matches!(warnings[3], QifError::MissingField { field: 'T', line: 6, record: 1, .. }));
+        assert!(matches!(warnings[4], QifError::BadHeader { line: 10, record: 2, .. }))
+    }
+
+    #[test]
+    fn transaction_parse_options_select_mode() {
+        let format = DateFormat::MonthDayFullYear;
+        let text = "D01/05/2004\r\nTtwelve\r\nPFake Street Electronics\r\nEOrphan memo\r\n^";
+
+        let error = Transaction::from_str_with(text, &ParseOptions::strict(&format, &NumberFormat::default())).unwrap_err();
+
+        assert!(matches!(error, QifError::BadAmount { line: 2, .. }));
+
+        let (transaction, warnings) = Transaction::from_str_with(text, &ParseOptions::lenient(&format, &NumberFormat::default())).unwrap();
+
+        assert_eq!(transaction.vendor, "Fake Street Electronics");
+        assert_eq!(transaction.amount, Money::zero());
+        assert!(transaction.splits.is_empty());
+        assert_eq!(warnings.iter().map(|warning| warning.line()).collect::<Vec<_>>(), vec![Some(2), Some(4), Some(1)]);
+        assert!(matches!(warnings[1], QifError::OrphanSplitField { .. }))
+    }
+
+    #[test]
+    fn reader_yields_records_as_they_are_read() {
+        let text = "!Option:AutoSwitch\r\n!Account\r\nNChecking\r\nTBank\r\n^\r\n!Clear:AutoSwitch\r\n!Account\r\nNSavings\r\nTBank\r\n^\r\n!Type:Bank\r\nD01/05/2004\r\nT-20.00\r\nPFake Street Electronics\r\n^\r\n!Type:Cat\r\nNGifts\r\n^\r\n!Type:Prices\r\n\"VLCR\",25.5,\"01/05/2004\"\r\n^\r\n";
+
+        let records: Vec<Record> = QifReader::new(text.as_bytes(), &ParseOptions::default())
+            .collect::<Result<Vec<Record>, QifError>>()
+            .unwrap();
+
+        assert_eq!(records.len(), 5);
+        assert!(matches!(&records[0], Record::Account(account) if account.name == "Checking"));
+        assert!(matches!(&records[1], Record::AccountStart(account, Type::Bank) if account.name == "Savings"));
+        assert!(matches!(&records[2], Record::Transaction(transaction) if transaction.vendor == "Fake Street Electronics"));
+        assert!(matches!(&records[3], Record::Category(category) if category.name == "Gifts"));
+        assert!(matches!(&records[4], Record::Price(price) if price.symbol == "VLCR"));
+
+        let qif = QIF::from_records(QifReader::new(text.as_bytes(), &ParseOptions::default())).unwrap();
+
+        assert_eq!(qif, QIF::from_str(text, &DateFormat::MonthDayFullYear, &NumberFormat::default()).unwrap())
+    }
+
+    #[test]
+    fn reader_stops_at_read_errors() {
+        let bytes: &[u8] = b"!Type:Bank\r\nD01/05/2004\r\nT-20.00\r\nPFake Street \xff\r\n^\r\nD01/06/2004\r\nT-30.00\r\nPSam Hill Credit Union\r\n^\r\n";
+
+        let mut reader = QifReader::new(bytes, &ParseOptions::default());
+
+        assert_eq!(reader.next().unwrap().unwrap(), Record::SectionStart(Type::Bank));
+        assert!(matches!(reader.next(), Some(Err(QifError::Io(_)))));
+        assert!(reader.next().is_none())
+    }
+
+    #[test]
+    fn writer_writes_list_headers_as_needed() {
+        let format = DateFormat::MonthDayFullYear;
+        let checking = Account::builder().set_name("Checking").set_type("Bank").build().unwrap();
+        let gifts = Category::builder().set_name("Gifts").build().unwrap();
+        let salary = Category::builder().set_name("Salary").set_income(true).build().unwrap();
+
+        let mut writer = QifWriter::new(vec![], &format, &NumberFormat::default());
+
+        writer.write_account(&checking).unwrap();
+        writer.write_category(&gifts).unwrap();
+        writer.write_category(&salary).unwrap();
+        writer.start_section(&Type::Bank).unwrap();
+
+        let text = String::from_utf8(writer.finish().unwrap()).unwrap();
+
+        assert_eq!(text, "!Option:AutoSwitch\r\n!Account\r\nNChecking\r\nTBank\r\n^\r\n\r\n!Clear:AutoSwitch\r\n!Type:Cat\r\nNGifts\r\n^\r\n\r\nNSalary\r\nI\r\n^\r\n\r\n!Type:Bank\r\n")
+    }
+
+    #[test]
+    fn writer_round_trips_records_from_reader() {
+        let format = DateFormat::MonthDayFullYear;
+        let text = "!Account\r\nNSavings\r\nTBank\r\n^\r\n!Type:Bank\r\nD01/05/2004\r\nT-20.00\r\nPFake Street Electronics\r\n^\r\n!Type:Cat\r\nNGifts\r\n^\r\n!Type:Prices\r\n\"VLCR\",25.5,\"01/05/2004\"\r\n^\r\n";
+
+        let mut writer = QifWriter::new(vec![], &format, &NumberFormat::default());
+
+        for record in QifReader::new(text.as_bytes(), &ParseOptions::default()) {
+            writer.write_record(&record.unwrap()).unwrap();
+        }
+
+        let written = String::from_utf8(writer.finish().unwrap()).unwrap();
+        let qif = QIF::from_str(text, &format, &NumberFormat::default()).unwrap();
+
+        assert_eq!(QIF::from_str(&written, &format, &NumberFormat::default()).unwrap(), qif);
+        assert!(written.find("!Type:Bank").unwrap() < written.find("!Type:Cat").unwrap())
+    }
+
+    #[test]
+    fn money_rounds_explicitly_and_checks_overflow() {
+        let amount = money("2.345");
+
+        assert_eq!(amount.rescale(2, RoundingMode::HalfEven), Some(money("2.34")));
+        assert_eq!(amount.rescale(2, RoundingMode::HalfUp), Some(money("2.35")));
+        assert_eq!(money("-2.345").rescale(2, RoundingMode::Floor), Some(money("-2.35")));
+        assert_eq!(money("-2.345").rescale(2, RoundingMode::TowardZero), Some(money("-2.34")));
+
+        assert_eq!(money("1.5"), money("1.50"));
+        assert_eq!(money("0.1").checked_add(money("0.2")), Some(money("0.3")));
+        assert_eq!(Money::new(i64::MAX, 0).checked_add(money("1")), None);
+        assert_eq!(Money::new(i64::MAX, 0).rescale(2, RoundingMode::HalfEven), None);
+        assert_eq!(Money::from("1e5"), None);
+        assert_eq!(money("-0.05").to_string(), "-0.05")
+    }
+
+    #[test]
+    fn percentage_splits_are_exact_amounts() {
+        let qif_entry = "D2/10/2024\r\nT-100.00\r\nPGrocer\r\nSFood\r\n%33.3\r\nSHousehold\r\n%66.7\r\n^";
+
+        let transaction = Transaction::from_str(qif_entry, &DateFormat::MonthDayFullYear, &NumberFormat::default()).unwrap();
+        let total = transaction.splits.iter()
+            .try_fold(Money::zero(), |total, split| total.checked_add(split.amount))
+            .unwrap();
+
+        assert_eq!(transaction.splits[0].amount, money("-33.30"));
+        assert_eq!(transaction.splits[1].amount, money("-66.70"));
+        assert_eq!(total, transaction.amount);
+        assert!(transaction.to_string(&DateFormat::MonthDayFullYear, &NumberFormat::default()).contains("$-66.70\r\n"))
+    }
+
+    #[test]
+    fn transaction_keeps_u_amount_and_precision() {
+        let qif_entry = "D2/10/2024\r\nT-12.35\r\nU-12.3456\r\nPBroker\r\nSFees\r\n$-4.125\r\nSTax\r\n$-8.2206\r\n^";
+
+        let transaction = Transaction::from_str(qif_entry, &DateFormat::MonthDayFullYear, &NumberFormat::default()).unwrap();
+        let written = transaction.to_string(&DateFormat::MonthDayFullYear, &NumberFormat::default());
+
+        assert_eq!(transaction.amount, money("-12.35"));
+        assert_eq!(transaction.u_amount, Some(money("-12.3456")));
+        assert!(written.starts_with("D02/10/2024\r\nT-12.35\r\nU-12.3456\r\n"));
+        assert!(written.contains("$-4.125\r\n"));
+        assert!(written.contains("$-8.2206\r\n"));
+
+        let only_u = Transaction::from_str("D2/10/2024\r\nU-3.5\r\nPBroker\r\n^", &DateFormat::MonthDayFullYear, &NumberFormat::default()).unwrap();
+
+        assert_eq!(only_u.amount, money("-3.50"));
+    }
+
+    #[test]
+    fn conflicting_t_and_u_amounts_are_reported() {
+        let qif_entry = "D2/10/2024\r\nT-12.35\r\nPBroker\r\nU-13.3456\r\n^";
+
+        let error = Transaction::from_str(qif_entry, &DateFormat::MonthDayFullYear, &NumberFormat::default()).unwrap_err();
+
+        assert!(matches!(error, QifError::AmountConflict { .. }));
+        assert_eq!(error.line(), Some(4));
+
+        let (transaction, warnings) = Transaction::from_str_with(qif_entry, &ParseOptions::lenient(&DateFormat::MonthDayFullYear, &NumberFormat::default())).unwrap();
+
+        assert_eq!(transaction.amount, money("-12.35"));
+        assert_eq!(transaction.u_amount, Some(money("-13.3456")));
+        assert_eq!(warnings.len(), 1);
+        assert!(Transaction::from_str("D2/10/2024\r\nT-12.34\r\nU-12.3456\r\nPBroker\r\n^", &DateFormat::MonthDayFullYear, &NumberFormat::default()).is_ok())
+    }
+
+    #[test]
+    fn currency_amounts_refuse_other_currencies() {
+        let euro = Currency::from("eur").unwrap();
+        let pound = Currency::from("GBP").unwrap();
+        let rate = ExchangeRate::new(pound, euro, money("1.17"));
+
+        assert_eq!(euro.to_str(), "EUR");
+        assert_eq!(Currency::from("EU"), None);
+        assert_eq!(Currency::from("€UR"), None);
+
+        let euros = CurrencyAmount::new(money("11.70"), euro);
+        let pounds = CurrencyAmount::new(money("10.00"), pound);
+
+        assert_eq!(euros.checked_add(pounds), Err(CurrencyError::Mismatch { expected: euro, found: pound }));
+        assert_eq!(rate.convert(pounds, RoundingMode::HalfEven), Ok(euros));
+        assert_eq!(rate.convert(euros, RoundingMode::HalfEven), Ok(pounds));
+        assert_eq!(euros.checked_add(rate.convert(pounds, RoundingMode::HalfEven).unwrap()).unwrap().to_string(), "23.40 EUR");
+
+        let transaction = Transaction::builder()
+            .set_date("02/10/2024", &DateFormat::MonthDayFullYear)
+            .set_vendor("Hotel")
+            .set_amount(money("-117.00"))
+            .set_exchange_rate(rate)
+            .build().unwrap();
+
+        assert_eq!(transaction.original_amount(RoundingMode::HalfEven), Some(CurrencyAmount::new(money("-100.00"), pound)));
+    }
+
+    #[test]
+    fn qif_balance_is_reported_in_base_currency() {
+        let qif_entry = "!Account\r\nNEuro Checking\r\nTBank\r\n^\r\n!Type:Bank\r\nD2/10/2024\r\nT100.00\r\nPSalary\r\n^\r\nD2/11/2024\r\nT-40.00\r\nPRent\r\n^\r\n!Account\r\nNPound Savings\r\nTBank\r\n^\r\n!Type:Bank\r\nD2/10/2024\r\nT50.00\r\nPInterest\r\n^\r\n";
+
+        let mut qif = QIF::from_str(qif_entry, &DateFormat::MonthDayFullYear, &NumberFormat::default()).unwrap();
+        let euro = Currency::from("EUR").unwrap();
+        let pound = Currency::from("GBP").unwrap();
+        let mut rates = RateTable::new();
+
+        assert_eq!(qif.balance_in(euro, &rates, RoundingMode::HalfEven), Err(CurrencyError::NoCurrency));
+
+        {
+            let mut sections = qif.sections_mut();
+            sections.next().unwrap().account.as_mut().unwrap().currency = Some(euro);
+            sections.next().unwrap().currency = Some(pound);
+        }
+
+        assert_eq!(qif.account_section("Euro Checking").unwrap().balance(), Ok(CurrencyAmount::new(money("60.00"), euro)));
+        assert_eq!(qif.balance_in(euro, &rates, RoundingMode::HalfEven), Err(CurrencyError::MissingRate { from: pound, to: euro }));
+
+        rates.add(ExchangeRate::new(pound, euro, money("1.17")));
+
+        assert_eq!(qif.balance_in(euro, &rates, RoundingMode::HalfEven), Ok(CurrencyAmount::new(money("118.50"), euro)));
+        assert_eq!(qif.balance_in(pound, &rates, RoundingMode::HalfEven), Ok(CurrencyAmount::new(money("101.28"), pound)))
+    }
+
+    #[test]
+    fn duplicate_transactions_are_kept_by_default() {
+        let qif_entry = "!Type:Bank\r\nD2/10/2024\r\nT-5.00\r\nPCoffee Shop\r\n^\r\nD2/10/2024\r\nT-5.00\r\nPCoffee Shop\r\n^\r\n!Type:Bank\r\nD2/10/2024\r\nT-5.00\r\nPCoffee Shop\r\n^\r\n";
+
+        let qif = QIF::from_str(qif_entry, &DateFormat::MonthDayFullYear, &NumberFormat::default()).unwrap();
+
+        assert_eq!(qif.sections_of(Type::Bank).map(|section| section.transactions.len()).sum::<usize>(), 3);
+
+        let options = ParseOptions::strict(&DateFormat::MonthDayFullYear, &NumberFormat::default()).with_merge_policy(MergePolicy::ExactMatch);
+        let (qif, _) = QIF::from_str_with(qif_entry, &options).unwrap();
+
+        assert_eq!(qif.sections_of(Type::Bank).map(|section| section.transactions.len()).sum::<usize>(), 1);
+    }
+
+    #[test]
+    fn key_match_policy_compares_date_amount_payee_and_number() {
+        let format = DateFormat::MonthDayFullYear;
+        let numbers = NumberFormat::default();
+        let existing = Section::from_str("!Type:Bank\r\nD2/10/2024\r\nT-5.00\r\nPCoffee Shop\r\nMLatte\r\n^\r\n", &format, &numbers).unwrap();
+        let incoming = Section::from_str("!Type:Bank\r\nD2/10/2024\r\nT-5.00\r\nPCoffee Shop\r\nMMocha\r\n^\r\nD2/10/2024\r\nN1001\r\nT-5.00\r\nPCoffee Shop\r\n^\r\n", &format, &numbers).unwrap();
+
+        let mut exact = existing.clone();
+        exact.merge(incoming.clone(), &MergePolicy::ExactMatch);
+
+        assert_eq!(exact.transactions.len(), 3);
+
+        let mut keyed = existing.clone();
+        keyed.merge(incoming.clone(), &MergePolicy::KeyMatch);
+
+        assert_eq!(keyed.transactions.len(), 2);
+        assert_eq!(keyed.transactions[0].memo, "Latte");
+        assert_eq!(keyed.transactions[1].check_number, Some(CheckNumber::Number(1001)));
+
+        let mut builder = QIF::builder();
+        builder.set_field(existing);
+
+        assert!(builder.update_field(incoming));
+        assert_eq!(builder.build().section(Type::Bank).unwrap().transactions.len(), 3)
+    }
+
+    #[test]
+    fn qif_keeps_the_layout_of_the_file() {
+        let format = DateFormat::MonthDayFullYear;
+        let coffee = "D02/10/2024\r\nT-5.00\r\nC\r\nN\r\nPCoffee Shop\r\nM\r\nACoffee Shop\r\nL\r\n^\r\n\r\n";
+        let text = format!("!Type:Bank\r\n{0}!Type:Cat\r\nNGifts\r\nE\r\n^\r\n\r\n!Type:Bank\r\n{0}!Option:Custom\r\n", coffee);
+
+        let qif = QIF::from_str(&text, &format, &NumberFormat::default()).unwrap();
+
+        assert_eq!(qif.items.len(), 4);
+        assert!(matches!(qif.items[1], QifItem::Categories(_)));
+        assert_eq!(qif.items[3], QifItem::Header(Header::from("!Option:Custom").unwrap()));
+        assert_eq!(qif.sections_of(Type::Bank).count(), 2);
+        assert_eq!(qif.to_string(&format, &NumberFormat::default()), text)
+    }
+
+    #[test]
+    fn qif_lookups_find_items_by_type() {
+        let format = DateFormat::MonthDayFullYear;
+        let text = "!Type:Cat\r\nNGifts\r\nE\r\n^\r\n!Account\r\nNChecking\r\nTBank\r\n^\r\n!Type:Bank\r\nD2/10/2024\r\nT-5.00\r\nPCoffee Shop\r\n^\r\n!Type:CCard\r\nD2/11/2024\r\nT-20.00\r\nPBook Shop\r\n^\r\n!Type:Cat\r\nNSalary\r\nI\r\n^\r\n";
+
+        let qif = QIF::from_str(text, &format, &NumberFormat::default()).unwrap();
+
+        assert_eq!(qif.categories().map(|category| category.name.as_str()).collect::<Vec<&str>>(), vec!["Gifts", "Salary"]);
+        assert_eq!(qif.sections_of(Type::Bank).count(), 1);
+        assert!(qif.section(Type::Bank).is_none());
+        assert_eq!(qif.account_section("Checking").unwrap().transactions[0].vendor, "Coffee Shop");
+        assert_eq!(qif.section(Type::CreditCard).unwrap().transactions[0].vendor, "Book Shop");
+
+        let mut builder = QIF::builder();
+        builder.items = qif.items.clone();
+        builder.set_field(Section::builder().set_type("CCard").build().unwrap());
+
+        let replaced = builder.build();
+
+        assert_eq!(replaced.items.len(), qif.items.len());
+        assert!(replaced.section(Type::CreditCard).unwrap().transactions.is_empty());
+        assert_eq!(replaced.items[2].as_section().unwrap().qif_type, Type::CreditCard)
+    }
+
+    #[test]
+    fn accounts_keep_types_that_are_not_known() {
+        let format = DateFormat::MonthDayFullYear;
+        let text = "NBrokerage\r\nT401(k)/403(b)\r\n^";
+
+        let account = Account::from_str(text, &format, &NumberFormat::default()).unwrap();
+
+        assert_eq!(account.account_type, Some(Type::Other(String::from("401(k)/403(b)"))));
+        assert_eq!(Account::from_str("NBrokerage\r\nT\r\n^", &format, &NumberFormat::default()).unwrap().account_type, None);
+        assert_eq!(account.to_string(&format, &NumberFormat::default()), text)
+    }
+
+    #[test]
+    fn values_are_read_like_amounts() {
+        let format = NumberFormat::default();
+
+        assert_eq!(format.parse_value("1,234.5"), Some(1234.5));
+        assert_eq!(format.parse_value("(2.5)"), Some(-2.5));
+        assert_eq!(format.parse_value("inf"), None);
+        assert_eq!(format.parse_value("NaN"), None);
+        assert_eq!(format.parse_value("1e5"), None);
+        assert_eq!(format.parse("-9223372036854775808"), Some(Money::new(i64::MIN, 0)));
+        assert_eq!(format.parse("9223372036854775808"), None);
+        assert_eq!(format.parse("(-9223372036854775808)"), None);
+        assert!(matches!(
+            Transaction::from_str("D2/10/2024\r\nT99999999999999999999.00\r\n^", &DateFormat::MonthDayFullYear, &format),
+            Err(QifError::BadAmount { line: 2, .. })))
+    }
+
+    #[test]
+    fn investment_transaction_keeps_u_amount_and_precision() {
+        let format = DateFormat::MonthDayFullYear;
+        let text = "D02/10/2024\r\nNBuy\r\nYVelociraptor Entertainment\r\nI25.125\r\nQ10.000\r\nT251.25\r\nU251.2500\r\n^";
+
+        let transaction = InvestmentTransaction::from_str(text, &format, &NumberFormat::default()).unwrap();
+
+        assert_eq!(transaction.quantity.unwrap().scale(), 3);
+        assert_eq!(transaction.u_amount, Some(money("251.25")));
+        assert_eq!(transaction.to_string(&format, &NumberFormat::default()), text);
+        assert!(matches!(
+            InvestmentTransaction::from_str("D2/10/2024\r\nNBuy\r\nT251.25\r\nU215.2500\r\n^", &format, &NumberFormat::default()),
+            Err(QifError::AmountConflict { line: 4, .. })))
+    }
+
+    #[test]
+    fn line_items_are_written_with_the_number_format() {
+        let european = NumberFormat::from("1.234,56").unwrap();
+
+        let line_item = LineItem::builder()
+        .set_description("Consultation")
+        .set_quantity(money("1.5"))
+        .set_price(money("75.125"))
+        .build().unwrap();
+
+        assert_eq!(line_item.to_string(&european), "XSConsultation\r\nXN\r\nX#1,5\r\nX$75,125");
+        assert_eq!(line_item.amount(RoundingMode::HalfEven), Some(money("112.688")));
+        assert_eq!(line_item.amount(RoundingMode::TowardZero), Some(money("112.687")))
+    }
+
+    #[test]
+    fn list_and_line_item_amounts_use_the_number_format() {
+        let today = Local::now();
+        let format = DateFormat::MonthDayFullYear;
+        let european = NumberFormat::from("1.234,56").unwrap();
+
+        let text = "!Type:Cat\r\nNSalary\r\nI\r\nB1.200,00\r\n^\r\n";
+        let qif = QIF::from_str(text, &format, &european).unwrap();
+
+        assert_eq!(qif.categories().next().unwrap().budget_amount, Some(money("1200.00")));
+        assert_eq!(qif.to_string(&format, &european), "!Type:Cat\r\nNSalary\r\nI\r\nB1200,00\r\n^\r\n\r\n");
+        assert!(matches!(QIF::from_str("!Type:Cat\r\nNSalary\r\nB1.200 apples\r\n^\r\n", &format, &NumberFormat::default()), Err(QifError::BadAmount { line: 3, .. })));
+
+        let invoice = format!("!Type:Invoice\r\nD{}\r\nT1.234,50\r\nPVelociraptor Entertainment\r\nXSConsultation\r\nX#1,5\r\nX$1.234,50\r\n^\r\n",
+        today.format(format.chrono_str()));
+
+        let qif = QIF::from_str(&invoice, &format, &european).unwrap();
+        let line_item = &qif.section(Type::Invoice).unwrap().invoices[0].line_items[0];
+
+        assert_eq!((line_item.quantity, line_item.price), (money("1.5"), money("1234.50")));
+        assert!(matches!(
+            QIF::from_str(&invoice.replace("X$1.234,50", "X$12 apples"), &format, &european),
+            Err(QifError::BadAmount { line: 7, record: 0, .. })))
+    }
+
+    #[test]
+    fn memorized_and_invoice_builders_can_leave_the_address_empty() {
+        let today = Local::now().format("%m/%d/%Y").to_string();
+
+        let memorized = MemorizedTransaction::builder()
+        .set_type("P")
+        .set_vendor("Fake Street Apartments")
+        .set_amount(money("-950.00"))
+        .build().unwrap();
+
+        let invoice = Invoice::builder()
+        .set_date(&today, &DateFormat::MonthDayFullYear)
+        .set_customer("Velociraptor Entertainment")
+        .set_amount(money("75.00"))
+        .build().unwrap();
+
+        assert!(memorized.address.is_empty());
+        assert!(invoice.address.is_empty());
+        assert_eq!(MemorizedTransaction::builder().set_type("P").set_vendor("Fake Street Apartments").set_vendor_as_address(true).set_amount(money("-950.00")).build().unwrap().address, vec!["Fake Street Apartments"])
+    }
+
+    #[test]
+    fn memorized_transactions_and_invoices_keep_every_field() {
+        let format = DateFormat::MonthDayFullYear;
+        let text = "KC\r\nD02/10/2024\r\nT-950.00\r\nU-950.0000\r\nC\r\nN1260\r\nPFake Street Apartments\r\nM\r\nAFake Street Apartments\r\nLRent\r\n^";
+
+        let memorized = MemorizedTransaction::from_str(text, &format, &NumberFormat::default()).unwrap();
+
+        assert_eq!(memorized.check_number, Some(CheckNumber::Number(1260)));
+        assert_eq!(memorized.u_amount, Some(money("-950.0000")));
+        assert!(memorized.date.is_some());
+        assert_eq!(memorized.to_string(&format, &NumberFormat::default()), text);
+
+        let invoice = Invoice::from_str("D02/10/2024\r\nU75.0000\r\nPVelociraptor Entertainment\r\n^", &format, &NumberFormat::default()).unwrap();
+
+        assert_eq!((invoice.amount, invoice.u_amount), (money("75.00"), Some(money("75.0000"))));
+        assert!(invoice.to_string(&format, &NumberFormat::default()).contains("\r\nU75.0000\r\n"))
+    }
+
+    #[test]
+    fn lists_keep_lines_that_are_not_recognized() {
+        let format = DateFormat::MonthDayFullYear;
+        let text = "!Account\r\nNChecking\r\nTBank\r\nXCustom\r\n^\r\n!Type:Bank\r\nD02/10/2024\r\nT-20.00\r\nC\r\nN\r\nPGrocer\r\nM\r\nAGrocer\r\nL\r\n^\r\n\r\n!Type:Cat\r\nNSalary\r\nI\r\nZ1\r\n^\r\n\r\n!Type:Class\r\nNBusiness\r\nQQuarterly\r\n^\r\n\r\n!Type:Security\r\nNVelociraptor Entertainment\r\nSVLCR\r\nXNYSE\r\n^\r\n\r\n";
+
+        let qif = QIF::from_str(text, &format, &NumberFormat::default()).unwrap();
+
+        assert_eq!(qif.account_section("Checking").unwrap().account.as_ref().unwrap().unknown_fields, vec!["XCustom"]);
+        assert_eq!(qif.categories().next().unwrap().unknown_fields, vec!["Z1"]);
+        assert_eq!(qif.classes().next().unwrap().unknown_fields, vec!["QQuarterly"]);
+        assert_eq!(qif.securities().next().unwrap().unknown_fields, vec!["XNYSE"]);
+        assert_eq!(qif.to_string(&format, &NumberFormat::default()), text)
+    }
+
+    #[test]
+    fn section_headers_are_written_back_where_they_were() {
+        let format = DateFormat::MonthDayFullYear;
+        let text = "!Option:Before\r\n!Type:Bank\r\n!Option:First\r\nD02/10/2024\r\nT-20.00\r\nC\r\nN\r\nPGrocer\r\nM\r\nAGrocer\r\nL\r\n^\r\n\r\n!Option:Between\r\nD02/11/2024\r\nT-5.00\r\nC\r\nN\r\nPBakery\r\nM\r\nABakery\r\nL\r\n^\r\n\r\n!Option:After\r\n!Type:Cat\r\nNSalary\r\n^\r\n\r\n";
+
+        let (qif, _) = QIF::from_str_with(text, &ParseOptions::lenient(&format, &NumberFormat::default())).unwrap();
+        let bank = qif.section(Type::Bank).unwrap();
+
+        assert_eq!(bank.unknown_fields, vec!["!Option:Before"]);
+        assert_eq!(bank.inner_unknown_fields, vec![(0, String::from("!Option:First")), (1, String::from("!Option:Between"))]);
+        assert_eq!(qif.to_string(&format, &NumberFormat::default()), text);
+
+        let section = Section::from_str_with(&text[..text.find("!Option:After").unwrap()], &ParseOptions::lenient(&format, &NumberFormat::default())).unwrap().0;
+
+        assert_eq!(&section, bank)
+    }
+
+    #[test]
+    fn records_before_any_header_are_reported_or_kept() {
+        let format = DateFormat::MonthDayFullYear;
+        let text = "NStray\r\nDNo header\r\n^\r\n\r\n!Type:Cat\r\nNSalary\r\n^\r\n\r\n";
+
+        let error = QIF::from_str(text, &format, &NumberFormat::default()).unwrap_err();
+
+        assert!(matches!(&error, QifError::MissingHeader { line: 1, record: 0, text } if text == "NStray"));
+
+        let (qif, warnings) = QIF::from_str_with(text, &ParseOptions::lenient(&format, &NumberFormat::default())).unwrap();
+
+        assert_eq!(qif.items[0], QifItem::Unknown(vec![String::from("NStray"), String::from("DNo header")]));
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(qif.to_string(&format, &NumberFormat::default()), text);
+        assert_eq!(QIF::from_str("\u{feff}!Type:Cat\r\nNSalary\r\n^\r\n", &format, &NumberFormat::default()).unwrap().categories().count(), 1)
+    }
+
+    #[test]
+    fn investment_section_round_trips_byte_for_byte() {
+        let format = DateFormat::MonthDayFullYear;
+        let text = "!Type:Invst\r\nD02/10/2024\r\nNBuy\r\nYVelociraptor Entertainment\r\nI25.125\r\nQ10\r\nT251.25\r\nCX\r\nMInitial purchase\r\nO4.95\r\nL[Sam Hill Credit Union]\r\n$256.20\r\n^\r\n\r\n";
+
+        let qif = QIF::from_str(text, &format, &NumberFormat::default()).unwrap();
+
+        assert_eq!(qif.section(Type::Investment).unwrap().investment_transactions[0].action, InvestmentAction::Buy);
+        assert_eq!(qif.to_string(&format, &NumberFormat::default()), text)
+    }
+
+    #[test]
+    fn memorized_section_round_trips_byte_for_byte() {
+        let format = DateFormat::MonthDayFullYear;
+        let text = "!Type:Memorized\r\nKP\r\nT-950.00\r\nC\r\nPFake Street Apartments\r\nMMonthly rent\r\nAFake Street Apartments\r\nLRent\r\n^\r\n\r\n";
+
+        let qif = QIF::from_str(text, &format, &NumberFormat::default()).unwrap();
+
+        assert_eq!(qif.section(Type::Memorized).unwrap().memorized_transactions[0].memorized_type, MemorizedTransactionType::Payment);
+        assert_eq!(qif.to_string(&format, &NumberFormat::default()), text)
+    }
+
+    #[test]
+    fn invoice_section_round_trips_byte_for_byte() {
+        let format = DateFormat::MonthDayFullYear;
+        let text = "!Type:Invoice\r\nD02/10/2024\r\nT162.00\r\nC\r\nN1001\r\nPVelociraptor Entertainment\r\nM\r\nAVelociraptor Entertainment\r\nLAccounts Receivable\r\nXI1\r\nXR8.25\r\nXSConsultation\r\nXNServices\r\nX#2\r\nX$75.00\r\nXF\r\nXSTravel\r\nXN\r\nX#1\r\nX$12.00\r\n^\r\n\r\n";
+
+        let qif = QIF::from_str(text, &format, &NumberFormat::default()).unwrap();
+
+        assert_eq!(qif.section(Type::Invoice).unwrap().invoices[0].line_items.len(), 2);
+        assert_eq!(qif.to_string(&format, &NumberFormat::default()), text)
+    }
+
+    #[test]
+    fn u_amounts_and_precision_round_trip_byte_for_byte() {
+        let format = DateFormat::MonthDayFullYear;
+        let text = concat!(
+            "!Type:Invst\r\nD02/10/2024\r\nNBuy\r\nYVelociraptor Entertainment\r\nI25.125\r\nQ10.000\r\nT251.25\r\nU251.2500\r\n^\r\n\r\n",
+            "!Type:Memorized\r\nKP\r\nT-950.00\r\nU-950.0000\r\nC\r\nPFake Street Apartments\r\nM\r\nAFake Street Apartments\r\nLRent\r\n^\r\n\r\n",
+            "!Type:Invoice\r\nD02/10/2024\r\nT162.00\r\nU162.0000\r\nC\r\nN\r\nPVelociraptor Entertainment\r\nM\r\nAVelociraptor Entertainment\r\nL\r\nXSTravel\r\nXN\r\nX#1.5\r\nX$12.000\r\n^\r\n\r\n");
+
+        let qif = QIF::from_str(text, &format, &NumberFormat::default()).unwrap();
+
+        assert_eq!(qif.section(Type::Investment).unwrap().investment_transactions[0].quantity.unwrap().scale(), 3);
+        assert_eq!(qif.to_string(&format, &NumberFormat::default()), text)
+    }
+
+    #[test]
+    fn memorized_dates_and_numbers_round_trip_byte_for_byte() {
+        let format = DateFormat::MonthDayFullYear;
+        let text = "!Type:Memorized\r\nKC\r\nD02/10/2024\r\nT-950.00\r\nC\r\nN1260\r\nPFake Street Apartments\r\nM\r\nAFake Street Apartments\r\nLRent\r\n^\r\n\r\n";
+
+        let qif = QIF::from_str(text, &format, &NumberFormat::default()).unwrap();
+
+        assert_eq!(qif.section(Type::Memorized).unwrap().memorized_transactions[0].check_number, Some(CheckNumber::Number(1260)));
+        assert_eq!(qif.to_string(&format, &NumberFormat::default()), text)
+    }
+
+    #[test]
+    fn category_lists_report_entries_without_names() {
+        let format = DateFormat::MonthDayFullYear;
+
+        assert!(matches!(Category::from_str("DHousing\r\nE\r\n^"), Err(QifError::MissingField { field: 'N', .. })));
+        assert!(matches!(
+            QIF::from_str("!Type:Cat\r\nNRent\r\n^\r\nDNo name\r\n^\r\n", &format, &NumberFormat::default()),
+            Err(QifError::MissingField { field: 'N', line: 4, record: 1, .. })));
+
+        let (qif, warnings) = QIF::from_str_with("!Type:Cat\r\nNRent\r\n^\r\nDNo name\r\n^\r\nNFood\r\n^\r\n", &ParseOptions::lenient(&format, &NumberFormat::default())).unwrap();
+
+        assert_eq!(qif.categories().map(|category| category.name.as_str()).collect::<Vec<&str>>(), vec!["Rent", "Food"]);
+        assert_eq!(warnings.len(), 1)
+    }
+
+    #[test]
+    fn class_lists_report_entries_without_names() {
+        let format = DateFormat::MonthDayFullYear;
+
+        assert!(matches!(Class::from_str("DBusiness\r\n^"), Err(QifError::MissingField { field: 'N', .. })));
+        assert!(matches!(
+            QIF::from_str("!Type:Class\r\nNBusiness\r\n^\r\nDNo name\r\n^\r\n", &format, &NumberFormat::default()),
+            Err(QifError::MissingField { field: 'N', line: 4, record: 1, .. })))
+    }
+
+    #[test]
+    fn parse_transaction_without_address_leaves_it_empty() {
+        let format = DateFormat::MonthDayFullYear;
+        let qif = QIF::from_str("!Type:Bank\r\nD01/02/2020\r\nT-5.00\r\nPCoffee\r\n^", &format, &NumberFormat::default()).unwrap();
+
+        let transaction = &qif.section(Type::Bank).unwrap().transactions[0];
+
+        assert_eq!(transaction.vendor, "Coffee");
+        assert!(transaction.address.is_empty())
+    }
+
+    #[test]
+    fn records_without_addresses_round_trip_byte_for_byte() {
+        let format = DateFormat::MonthDayFullYear;
+        let text = concat!(
+            "!Type:Bank\r\nD01/02/2020\r\nT-5.00\r\nC\r\nN\r\nPCoffee\r\nM\r\nL\r\n^\r\n\r\n",
+            "!Type:Memorized\r\nKP\r\nT-950.00\r\nC\r\nPFake Street Apartments\r\nM\r\nLRent\r\n^\r\n\r\n",
+            "!Type:Invoice\r\nD02/10/2024\r\nT12.00\r\nC\r\nN1001\r\nPVelociraptor Entertainment\r\nM\r\nLAccounts Receivable\r\nXSTravel\r\nXN\r\nX#1\r\nX$12.00\r\n^\r\n\r\n");
+
+        let qif = QIF::from_str(text, &format, &NumberFormat::default()).unwrap();
+
+        assert!(qif.section(Type::Bank).unwrap().transactions[0].address.is_empty());
+        assert!(qif.section(Type::Memorized).unwrap().memorized_transactions[0].address.is_empty());
+        assert!(qif.section(Type::Invoice).unwrap().invoices[0].address.is_empty());
+        assert_eq!(qif.to_string(&format, &NumberFormat::default()), text)
+    }
+
+    #[test]
+    fn accounts_replaced_before_their_section_are_kept() {
+        let format = DateFormat::MonthDayFullYear;
+        let text = "!Account\r\nNChecking\r\nTBank\r\n^\r\nNSavings\r\nTBank\r\n^\r\n!Type:Bank\r\nD01/05/2004\r\nT-20.00\r\nPFake Street Electronics\r\n^\r\n";
+
+        let qif = QIF::from_str(text, &format, &NumberFormat::default()).unwrap();
+
+        assert_eq!(qif.accounts().map(|account| account.name.as_str()).collect::<Vec<_>>(), vec!["Checking"]);
+        assert_eq!(qif.account_section("Savings").unwrap().transactions.len(), 1)
+    }
+
+    #[test]
+    fn accounts_followed_by_a_list_are_kept() {
+        let format = DateFormat::MonthDayFullYear;
+        let text = "!Account\r\nNChecking\r\nTBank\r\n^\r\n!Type:Cat\r\nNGifts\r\n^\r\n";
+
+        let qif = QIF::from_str(text, &format, &NumberFormat::default()).unwrap();
+
+        assert_eq!(qif.accounts().map(|account| account.name.as_str()).collect::<Vec<_>>(), vec!["Checking"]);
+        assert_eq!(qif.categories().count(), 1)
+    }
+
+    #[test]
+    fn accounts_at_the_end_of_the_file_are_kept() {
+        let format = DateFormat::MonthDayFullYear;
+        let text = "!Account\r\nNOnly\r\nTBank\r\n^";
+
+        let qif = QIF::from_str(text, &format, &NumberFormat::default()).unwrap();
+        let records: Vec<Record> = QifReader::new(text.as_bytes(), &ParseOptions::default())
+            .collect::<Result<Vec<Record>, QifError>>()
+            .unwrap();
+
+        assert_eq!(qif.accounts().map(|account| account.name.as_str()).collect::<Vec<_>>(), vec!["Only"]);
+        assert_eq!(records.len(), 1);
+        assert!(matches!(&records[0], Record::Account(account) if account.name == "Only"))
+    }
+
+    #[test]
+    fn records_after_unknown_headers_are_not_read_as_the_section_before() {
+        let format = DateFormat::MonthDayFullYear;
+        let text = "!Type:Bank\r\nD01/05/2004\r\nT-20.00\r\nPFake Street Electronics\r\n^\r\n!Type:Tag\r\nNVacation\r\nDTrip to Maine\r\n^\r\n";
+
+        let (qif, warnings) = QIF::from_str_with(text, &ParseOptions::lenient(&format, &NumberFormat::default())).unwrap();
+
+        assert_eq!(qif.section(Type::Bank).unwrap().transactions.len(), 1);
+        assert_eq!(qif.items.last(), Some(&QifItem::Unknown(vec![String::from("NVacation"), String::from("DTrip to Maine")])));
+        assert_eq!(warnings.len(), 1);
+        assert!(matches!(warnings[0], QifError::BadHeader { line: 6, record: 1, .. }));
+        assert!(qif.to_string(&format, &NumberFormat::default()).ends_with("!Type:Tag\r\nNVacation\r\nDTrip to Maine\r\n^\r\n\r\n"))
+    }
+
+    #[test]
+    fn money_from_f64_rounds_large_amounts_with_the_rounding_mode() {
+        assert_eq!(Money::from_f64(100.7, 0, RoundingMode::HalfUp), Some(Money::new(101, 0)));
+        assert_eq!(Money::from_f64(-100.7, 0, RoundingMode::Floor), Some(Money::new(-101, 0)));
+        assert_eq!(Money::from_f64(123456789.125, 2, RoundingMode::HalfEven), Some(money("123456789.12")));
+        assert_eq!(Money::from_f64(123456789.125, 2, RoundingMode::HalfUp), Some(money("123456789.13")));
+        assert_eq!(Money::from_f64(1e19, 0, RoundingMode::HalfEven), None)
+    }
+
+    #[test]
+    fn merged_headers_stay_in_front_of_the_records_they_came_before() {
+        let format = DateFormat::MonthDayFullYear;
+        let options = ParseOptions::lenient(&format, &NumberFormat::default());
+        let mut existing = Section::from_str("!Type:Bank\r\nD2/10/2024\r\nT-5.00\r\nPCoffee Shop\r\n^\r\n", &format, &NumberFormat::default()).unwrap();
+        let (incoming, _) = Section::from_str_with("!Type:Bank\r\nD2/10/2024\r\nT-5.00\r\nPCoffee Shop\r\n^\r\n!Option:Between\r\nD2/11/2024\r\nT-3.00\r\nPBakery\r\n^\r\n!Option:After\r\n", &options).unwrap();
+
+        existing.merge(incoming, &MergePolicy::ExactMatch);
+
+        assert_eq!(existing.transactions.len(), 2);
+        assert_eq!(existing.transactions[1].vendor, "Bakery");
+        assert_eq!(existing.inner_unknown_fields, vec![(1, String::from("!Option:Between")), (2, String::from("!Option:After"))])
+    }
+
+    #[test]
+    fn qif_builder_puts_categories_before_sections_added_first() {
+        let format = DateFormat::MonthDayFullYear;
+
+        let coffee = Transaction::builder()
+        .set_date("02/10/2024", &format)
+        .set_vendor("Coffee Shop")
+        .set_category("Gifts")
+        .set_amount(money("-5.00"))
+        .build().unwrap();
+
+        let qif = QIF::builder()
+        .add_section(Section::builder()
+            .set_type("Bank")
+            .add_transaction(coffee)
+            .build().unwrap())
+        .add_category(Category::builder().set_name("Gifts").set_expense(true).build().unwrap())
+        .add_category(Category::builder().set_name("Salary").set_income(true).build().unwrap())
+        .build();
+
+        assert_eq!(qif.categories().count(), 2);
+        assert!(qif.to_string(&format, &NumberFormat::default()).starts_with("!Type:Cat\r\nNGifts\r\nE\r\n^\r\n\r\nNSalary\r\nI\r\n^\r\n\r\n!Type:Bank\r\n"))
     }
 }
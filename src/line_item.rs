@@ -0,0 +1,135 @@
+use crate::{NumberFormat, Money, RoundingMode};
+
+use std::fmt;
+
+/// structure that represents a line item on an invoice
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineItem {
+    pub description: String,
+    pub category: Option<String>,
+    pub quantity: Money,
+    pub price: Money,
+    pub taxable: bool
+}
+
+impl LineItem {
+    /**
+     * builder method to easily create line items.
+     * The quantity defaults to 1 if it is not set.
+     *
+     * Usage would look like this:
+     *
+     * ```ignore
+     * let consultation = LineItem::builder()
+     *     .set_description("Consultation")
+     *     .set_category("Services")
+     *     .set_quantity(Money::from("2").unwrap())
+     *     .set_price(Money::from("75.00").unwrap())
+     *     .set_taxable(true)
+     *     .build().unwrap();
+     * ```
+     */
+    pub fn builder() -> LineItemBuilder {
+        LineItemBuilder::new()
+    }
+
+    /**
+     * retrieve the total of the line item, which is the quantity times the price,
+     * kept to the decimal places of the price and rounded in the given mode.
+     * Returns None if the total is too large to hold.
+     */
+    pub fn amount(&self, rounding: RoundingMode) -> Option<Money> {
+        self.price.checked_mul(self.quantity, rounding)
+    }
+
+    /// writes the line item, keeping the decimal places the quantity and price were read with.
+    pub fn to_string(&self, nf: &NumberFormat) -> String {
+        let mut content = format!("XS{}\r\nXN{}\r\nX#{}\r\nX${}",
+            self.description,
+            self.category.clone().unwrap_or_default(),
+            nf.format_exact(self.quantity),
+            nf.format_exact(self.price));
+
+        if self.taxable {
+            content.push_str("\r\nXF");
+        }
+
+        content
+    }
+}
+
+impl fmt::Display for LineItem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_string(&NumberFormat::default()))
+    }
+}
+
+pub struct LineItemBuilder {
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub quantity: Option<Money>,
+    pub price: Option<Money>,
+    pub taxable: bool
+}
+
+impl Default for LineItemBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LineItemBuilder {
+    pub fn new() -> Self {
+        LineItemBuilder {
+            description: None,
+            category: None,
+            quantity: None,
+            price: None,
+            taxable: false
+        }
+    }
+
+    pub fn set_description(&mut self, description: &str) -> &mut Self {
+        self.description = if description.is_empty() {
+            None
+        } else {
+            Some(description.to_string())
+        };
+        self
+    }
+
+    pub fn set_category(&mut self, category: &str) -> &mut Self {
+        self.category = if category.is_empty() {
+            None
+        } else {
+            Some(category.to_string())
+        };
+        self
+    }
+
+    pub fn set_quantity(&mut self, quantity: Money) -> &mut Self {
+        self.quantity = Some(quantity);
+        self
+    }
+
+    pub fn set_price(&mut self, price: Money) -> &mut Self {
+        self.price = Some(price);
+        self
+    }
+
+    pub fn set_taxable(&mut self, taxable: bool) -> &mut Self {
+        self.taxable = taxable;
+        self
+    }
+
+    /// creates the line item, provided that a price was given.
+    pub fn build(&self) -> Option<LineItem> {
+        self.price.map(|price| LineItem {
+            description: self.description.clone().unwrap_or_default(),
+            category: self.category.clone(),
+            quantity: self.quantity.unwrap_or(Money::new(1, 0)),
+            price,
+            taxable: self.taxable
+        })
+    }
+}
\ No newline at end of file
@@ -0,0 +1,374 @@
+use chrono::prelude::*;
+
+use crate::{CategoryRef, CheckNumber, MemorizedTransactionType, Amortization, Transaction, TransactionStatus, Split, SplitBuilder, DateFormat, NumberFormat, TransactionBuildingError, QifError, ParseOptions, Money};
+use crate::transaction::{parse_field, add_splits, address_to_string, trim_address, unknown_fields_to_string, u_amount_to_string, parse_date, read_date, read_amount, read_value, parse_lines, drop_first_character_from};
+
+use std::fmt;
+
+/// structure that represents a memorized transaction (payee template) in a QIF file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MemorizedTransaction {
+    pub memorized_type: MemorizedTransactionType,
+    /// the date of the transaction the template was memorized from, which Quicken does not always write.
+    pub date: Option<DateTime<Local>>,
+    pub check_number: Option<CheckNumber>,
+    pub vendor: String,
+    pub address: Vec<String>,
+    pub amount: Money,
+    /// the amount Quicken writes in the U field, if there was one.
+    pub u_amount: Option<Money>,
+    pub category: Option<CategoryRef>,
+    pub memo: String,
+    pub status: Option<TransactionStatus>,
+    pub splits: Vec<Split>,
+    pub amortization: Option<Amortization>,
+    pub unknown_fields: Vec<String>
+}
+
+impl MemorizedTransaction {
+    /**
+     * This method creates a builder that will help faciliate
+     * in a creating memorized transaction.
+     *
+     * Memorized transactions can then be made like this:
+     *
+     * ```ignore
+     * let rent = MemorizedTransaction::builder()
+     *     .set_type("P")
+     *     .set_vendor("Fake Street Apartments")
+     *     .set_category("Rent")
+     *     .set_amount(Money::from("-950.00").unwrap())
+     *     .build().unwrap();
+     * ```
+     */
+    pub fn builder() -> MemorizedTransactionBuilder {
+        MemorizedTransactionBuilder::new()
+    }
+
+    pub fn to_string(&self, df: &DateFormat, nf: &NumberFormat) -> String {
+        let mut content = format!("K{}\r\n{}T{}\r\n{}C{}\r\n{}P{}\r\nM{}\r\n{}L{}\r\n",
+            self.memorized_type.to_str(),
+            self.date.map(|date| format!("D{}\r\n", date.format(df.chrono_str()))).unwrap_or_default(),
+            nf.format(self.amount),
+            u_amount_to_string(&self.u_amount, nf),
+            if let Some(status) = &self.status {
+                status.to_str()
+            } else {
+                ""
+            },
+            self.check_number.as_ref().map(|check_number| format!("N{}\r\n", check_number)).unwrap_or_default(),
+            self.vendor,
+            self.memo,
+            address_to_string(&self.address),
+            self.category.as_ref().map(|category| category.to_string()).unwrap_or_default());
+
+        for split in &self.splits {
+            content.push_str(&format!("{}\r\n", split.to_string(nf)));
+        }
+
+        if let Some(amortization) = &self.amortization {
+            if let Some(first_payment_date) = amortization.first_payment_date {
+                content.push_str(&format!("1{}\r\n", first_payment_date.format(df.chrono_str())));
+            }
+
+            if let Some(total_years) = amortization.total_years {
+                content.push_str(&format!("2{}\r\n", total_years));
+            }
+
+            if let Some(payments_made) = amortization.payments_made {
+                content.push_str(&format!("3{}\r\n", payments_made));
+            }
+
+            if let Some(periods_per_year) = amortization.periods_per_year {
+                content.push_str(&format!("4{}\r\n", periods_per_year));
+            }
+
+            if let Some(interest_rate) = amortization.interest_rate {
+                content.push_str(&format!("5{}\r\n", nf.format_value(interest_rate)));
+            }
+
+            if let Some(current_balance) = amortization.current_balance {
+                content.push_str(&format!("6{}\r\n", nf.format(current_balance)));
+            }
+
+            if let Some(original_amount) = amortization.original_amount {
+                content.push_str(&format!("7{}\r\n", nf.format(original_amount)));
+            }
+        }
+
+        content.push_str(&unknown_fields_to_string(&self.unknown_fields));
+        content.push('^');
+
+        content
+    }
+
+    /**
+     * parses a memorized transaction.
+     * Fields shared with regular transactions, including splits,
+     * are handled by the same logic that parses transactions.
+     */
+    pub fn from_str(s: &str, df: &DateFormat, nf: &NumberFormat) -> Result<Self, QifError> {
+        Self::from_lines(s.lines(), &ParseOptions::strict(df, nf), &mut vec![])
+    }
+
+    /// parses the record from its lines, which is how the parser hands records over.
+    pub(crate) fn from_lines<'a>(lines: impl Iterator<Item = &'a str>, options: &ParseOptions, warnings: &mut Vec<QifError>) -> Result<Self, QifError> {
+        let (df, nf) = (&options.date_format, &options.number_format);
+        let lines: Vec<&str> = lines.collect();
+        let mut builder = MemorizedTransaction::builder();
+        let mut transaction_builder = Transaction::builder();
+        let mut split_builders: Vec<SplitBuilder> = vec![];
+        let mut amortization = Amortization::default();
+
+        parse_lines(&lines, &options.mode, warnings, |line| {
+            match line {
+                content if content.starts_with("K") => {
+                    builder.set_type(&drop_first_character_from(content));
+                },
+                content if content.starts_with("1") => {
+                    amortization.first_payment_date = read_date(content, df)?;
+                },
+                content if content.starts_with("2") => {
+                    amortization.total_years = read_count(content)?;
+                },
+                content if content.starts_with("3") => {
+                    amortization.payments_made = read_count(content)?;
+                },
+                content if content.starts_with("4") => {
+                    amortization.periods_per_year = read_count(content)?;
+                },
+                content if content.starts_with("5") => {
+                    amortization.interest_rate = read_value(content, nf)?;
+                },
+                content if content.starts_with("6") => {
+                    amortization.current_balance = read_amount(content, nf)?;
+                },
+                content if content.starts_with("7") => {
+                    amortization.original_amount = read_amount(content, nf)?;
+                },
+                content => parse_field(content, &mut transaction_builder, &mut split_builders, df, nf)?
+            }
+
+            Ok(())
+        })?;
+
+        add_splits(&mut transaction_builder, split_builders);
+
+        builder.date = transaction_builder.date;
+        builder.check_number = transaction_builder.check_number;
+        builder.vendor = transaction_builder.vendor;
+        builder.address = transaction_builder.address;
+        builder.amount = transaction_builder.amount;
+        builder.u_amount = transaction_builder.u_amount;
+        builder.category = transaction_builder.category;
+        builder.memo = transaction_builder.memo;
+        builder.status = transaction_builder.status;
+        builder.splits = transaction_builder.splits;
+        builder.unknown_fields = transaction_builder.unknown_fields;
+
+        if !amortization.is_empty() {
+            builder.set_amortization(amortization);
+        }
+
+        builder.build().map_err(|error| QifError::missing_field(error.field(), &lines))
+    }
+}
+
+/// reads a whole number of years or payments, where an empty field means there is no number.
+fn read_count(line: &str) -> Result<Option<u32>, QifError> {
+    let value = drop_first_character_from(line);
+
+    if value.trim().is_empty() {
+        Ok(None)
+    } else {
+        value.trim().parse::<u32>().map(Some).map_err(|_| QifError::bad_amount(line))
+    }
+}
+
+impl fmt::Display for MemorizedTransaction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_string(&DateFormat::MonthDayFullYear, &NumberFormat::default()))
+    }
+}
+
+pub struct MemorizedTransactionBuilder {
+    pub memorized_type: Option<MemorizedTransactionType>,
+    pub date: Option<DateTime<Local>>,
+    pub check_number: Option<CheckNumber>,
+    pub vendor: Option<String>,
+    pub address: Vec<String>,
+    pub vendor_as_address: bool,
+    pub amount: Option<Money>,
+    pub u_amount: Option<Money>,
+    pub category: Option<CategoryRef>,
+    pub memo: Option<String>,
+    pub status: Option<TransactionStatus>,
+    pub splits: Vec<Split>,
+    pub amortization: Option<Amortization>,
+    pub unknown_fields: Vec<String>
+}
+
+impl Default for MemorizedTransactionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemorizedTransactionBuilder {
+    pub fn new() -> Self {
+        MemorizedTransactionBuilder {
+            memorized_type: None,
+            date: None,
+            check_number: None,
+            vendor: None,
+            address: vec![],
+            vendor_as_address: false,
+            amount: None,
+            u_amount: None,
+            category: None,
+            memo: None,
+            status: None,
+            splits: vec![],
+            amortization: None,
+            unknown_fields: vec![]
+        }
+    }
+
+    // builder functions to set various fields.
+    pub fn set_type(&mut self, memorized_type: &str) -> &mut Self {
+        self.memorized_type = MemorizedTransactionType::from(memorized_type);
+        self
+    }
+
+    pub fn set_date(&mut self, date: &str, format: &DateFormat) -> &mut Self {
+        self.date = parse_date(date, format);
+        self
+    }
+
+    /// sets the number field from text, such as 1260, ATM, or DEP, where a check number of 0 is no number.
+    pub fn set_check_reference(&mut self, check_reference: &str) -> &mut Self {
+        self.check_number = match CheckNumber::from(check_reference) {
+            Some(CheckNumber::Number(0)) => None,
+            check_number => check_number
+        };
+        self
+    }
+
+    pub fn set_vendor(&mut self, vendor: &str) -> &mut Self {
+        self.vendor = if vendor.is_empty() {
+            None
+        } else {
+            Some(vendor.to_string())
+        };
+        self
+    }
+
+    /// sets the address to a single line, replacing any lines that were already added.
+    pub fn set_address(&mut self, address: &str) -> &mut Self {
+        self.address = if address.is_empty() {
+            vec![]
+        } else {
+            vec![String::from(address)]
+        };
+        self
+    }
+
+    pub fn add_address_line(&mut self, line: &str) -> &mut Self {
+        self.address.push(String::from(line));
+        self
+    }
+
+    /**
+     * decides if the vendor should be used as the address
+     * when no address is given, which is only done when asked for.
+     */
+    pub fn set_vendor_as_address(&mut self, vendor_as_address: bool) -> &mut Self {
+        self.vendor_as_address = vendor_as_address;
+        self
+    }
+
+    pub fn set_amount(&mut self, amount: Money) -> &mut Self {
+        self.amount = Some(amount);
+        self
+    }
+
+    /// sets the amount written in the U field, which is used as the amount if there is no T amount.
+    pub fn set_u_amount(&mut self, u_amount: Money) -> &mut Self {
+        self.u_amount = Some(u_amount);
+        self
+    }
+
+    pub fn set_category(&mut self, category: &str) -> &mut Self {
+        self.category = CategoryRef::from(category);
+        self
+    }
+
+    pub fn set_memo(&mut self, memo: &str) -> &mut Self {
+        self.memo = if memo.is_empty() {
+            None
+        } else {
+            Some(String::from(memo))
+        };
+        self
+    }
+
+    pub fn set_status(&mut self, status: &str) -> &mut Self {
+        self.status = TransactionStatus::from(status);
+        self
+    }
+
+    pub fn add_split(&mut self, split: Split) -> &mut Self {
+        self.splits.push(split);
+        self
+    }
+
+    pub fn set_amortization(&mut self, amortization: Amortization) -> &mut Self {
+        self.amortization = Some(amortization);
+        self
+    }
+
+    pub fn add_unknown_field(&mut self, field: &str) -> &mut Self {
+        self.unknown_fields.push(String::from(field));
+        self
+    }
+
+    /**
+     * this function is used to actually create the memorized transaction.
+     * It will return an error if there is no type, vendor, or amount provided.
+     */
+    pub fn build(&self) -> Result<MemorizedTransaction, TransactionBuildingError> {
+        if let Some(memorized_type) = self.memorized_type.clone() {
+            if let Some(vendor) = self.vendor.clone() {
+                if let Some(amount) = self.amount.or(self.u_amount) {
+                    let address = trim_address(&self.address);
+
+                    Ok(MemorizedTransaction {
+                        memorized_type,
+                        date: self.date,
+                        check_number: self.check_number.clone(),
+                        vendor: vendor.clone(),
+                        address: if address.is_empty() && self.vendor_as_address {
+                            vec![vendor]
+                        } else {
+                            address
+                        },
+                        amount,
+                        u_amount: self.u_amount,
+                        category: self.category.clone(),
+                        memo: self.memo.clone().unwrap_or_default(),
+                        status: self.status.clone(),
+                        splits: self.splits.clone(),
+                        amortization: self.amortization.clone(),
+                        unknown_fields: self.unknown_fields.clone()
+                    })
+                } else {
+                    Err(TransactionBuildingError::NoAmount)
+                }
+            } else {
+                Err(TransactionBuildingError::NoVendor)
+            }
+        } else {
+            Err(TransactionBuildingError::NoMemorizedType)
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,32 @@
+/// enumeration that houses the possible kinds of a memorized transaction.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MemorizedTransactionType {
+    Check,
+    Deposit,
+    Payment,
+    Investment,
+    Electronic
+}
+
+impl MemorizedTransactionType {
+    pub fn from(s: &str) -> Option<Self> {
+        match s.to_string() {
+            value if value == "C" => Some(Self::Check),
+            value if value == "D" => Some(Self::Deposit),
+            value if value == "P" => Some(Self::Payment),
+            value if value == "I" => Some(Self::Investment),
+            value if value == "E" => Some(Self::Electronic),
+            _ => None
+        }
+    }
+
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Check => "C",
+            Self::Deposit => "D",
+            Self::Payment => "P",
+            Self::Investment => "I",
+            Self::Electronic => "E"
+        }
+    }
+}
\ No newline at end of file
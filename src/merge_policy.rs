@@ -0,0 +1,70 @@
+use chrono::prelude::*;
+
+use crate::{CheckNumber, Money, Transaction, InvestmentTransaction, MemorizedTransaction, Invoice};
+
+/**
+ * enumeration for how records are merged into a section that already has records,
+ * which happens when a file has more than one section of the same type,
+ * or when sections are merged with Section::merge.
+ *
+ * Every record is kept by default, since two real transactions can be exactly alike,
+ * such as two coffees bought from the same place on the same day.
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum MergePolicy {
+    /// keeps every record, even if an equal one is already present.
+    #[default]
+    KeepAll,
+    /// drops a record if one equal to it in every field is already present.
+    ExactMatch,
+    /// drops a record if one with the same date, amount, payee, and check number is already present.
+    KeyMatch
+}
+
+/// the fields compared by MergePolicy::KeyMatch, which are the date, amount, payee, and check number.
+pub(crate) type MergeKey<'a> = (Option<DateTime<Local>>, Option<Money>, &'a str, Option<&'a CheckNumber>);
+
+/// trait for the records that are kept in sections, and so can be merged.
+pub(crate) trait Mergeable: PartialEq {
+    fn merge_key(&self) -> MergeKey<'_>;
+}
+
+impl MergePolicy {
+    /// checks if a record should be added to those already present, rather than dropped as a duplicate of one of them.
+    pub(crate) fn keeps<T: Mergeable>(&self, existing: &[T], record: &T) -> bool {
+        match self {
+            Self::KeepAll => true,
+            Self::ExactMatch => !existing.contains(record),
+            Self::KeyMatch => {
+                let key = record.merge_key();
+
+                !existing.iter().any(|existing_record| existing_record.merge_key() == key)
+            }
+        }
+    }
+}
+
+impl Mergeable for Transaction {
+    fn merge_key(&self) -> MergeKey<'_> {
+        (Some(self.date), Some(self.amount), &self.vendor, self.check_number.as_ref())
+    }
+}
+
+/// investment transactions use the security as the payee when there is no payee.
+impl Mergeable for InvestmentTransaction {
+    fn merge_key(&self) -> MergeKey<'_> {
+        (Some(self.date), self.amount, self.payee.as_deref().or(self.security.as_deref()).unwrap_or_default(), None)
+    }
+}
+
+impl Mergeable for MemorizedTransaction {
+    fn merge_key(&self) -> MergeKey<'_> {
+        (None, Some(self.amount), &self.vendor, None)
+    }
+}
+
+impl Mergeable for Invoice {
+    fn merge_key(&self) -> MergeKey<'_> {
+        (Some(self.date), Some(self.amount), &self.customer, self.number.as_ref())
+    }
+}
\ No newline at end of file
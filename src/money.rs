@@ -0,0 +1,244 @@
+use std::{cmp::Ordering, fmt, hash::{Hash, Hasher}};
+
+use crate::RoundingMode;
+
+/// the most decimal places an amount can have, which keeps every scale within an i64.
+const MAX_SCALE: u32 = 18;
+
+/**
+ * structure that represents an exact amount of money,
+ * kept as a whole number of units at a given number of decimal places,
+ * so -20.50 is -2050 at a scale of 2.
+ *
+ * Amounts are equal when they are worth the same, so 20.5 equals 20.50,
+ * and arithmetic is checked, giving None instead of overflowing.
+ * Results that need fewer decimal places than they would otherwise have
+ * are rounded with an explicit RoundingMode.
+ *
+ * ```ignore
+ * let total = Money::from("-1234.56").unwrap();
+ * let share = total.percentage(Money::from("18.9").unwrap(), RoundingMode::HalfEven).unwrap();
+ *
+ * assert_eq!(share, Money::new(-23333, 2));
+ * ```
+ */
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Money {
+    value: i64,
+    scale: u32
+}
+
+impl Money {
+    /// create an amount from a whole number of units and the number of decimal places, e.g. Money::new(-2050, 2) for -20.50.
+    pub fn new(value: i64, scale: u32) -> Self {
+        Money { value, scale: scale.min(MAX_SCALE) }
+    }
+
+    /**
+     * parses a plain decimal number like -1234.56, keeping as many decimal places as it is written with.
+     * Separators, currency symbols, and exponents are not accepted,
+     * since NumberFormat takes care of those.
+     */
+    pub fn from(s: &str) -> Option<Self> {
+        let text = s.trim();
+
+        let (is_negative, digits) = match text.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, text.strip_prefix('+').unwrap_or(text))
+        };
+
+        let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+
+        if whole.is_empty() && fraction.is_empty() || fraction.len() > MAX_SCALE as usize {
+            return None
+        }
+
+        if !whole.chars().chain(fraction.chars()).all(|character| character.is_ascii_digit()) {
+            return None
+        }
+
+        // negative amounts are built up as negative numbers, so the smallest amount can be read.
+        let sign = if is_negative { -1 } else { 1 };
+        let mut value: i64 = 0;
+
+        for digit in whole.chars().chain(fraction.chars()) {
+            value = value.checked_mul(10)?.checked_add(sign * digit.to_digit(10)? as i64)?;
+        }
+
+        Some(Money::new(value, fraction.len() as u32))
+    }
+
+    /// create an amount from a float, rounded to the given number of decimal places, returning None if it does not fit.
+    pub fn from_f64(amount: f64, scale: u32, rounding: RoundingMode) -> Option<Self> {
+        let scale = scale.min(MAX_SCALE);
+        let scaled = amount * 10f64.powi(scale as i32);
+
+        if !scaled.is_finite() || scaled.abs() >= i64::MAX as f64 {
+            return None
+        }
+
+        // as many decimal places are written as still fit, which is more than the float holds, so the rounding mode sees every digit.
+        let digits = (scaled.abs() as i64).to_string().len() as u32;
+        let exact = Money::from(&format!("{:.*}", MAX_SCALE.saturating_sub(digits) as usize, scaled))?;
+
+        Some(Money::new(exact.rescale(0, rounding)?.value, scale))
+    }
+
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    /// retrieve the amount as a whole number of units at its scale.
+    pub fn value(&self) -> i64 {
+        self.value
+    }
+
+    /// retrieve the number of decimal places the amount is kept with.
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.value == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.value < 0
+    }
+
+    /// retrieve the amount as a float, which may not be exact.
+    pub fn to_f64(&self) -> f64 {
+        self.value as f64 / 10f64.powi(self.scale as i32)
+    }
+
+    /**
+     * gives the amount with the given number of decimal places,
+     * rounding in the given mode if there are fewer than now.
+     * Returns None if the amount is too large to have that many.
+     */
+    pub fn rescale(&self, scale: u32, rounding: RoundingMode) -> Option<Self> {
+        let scale = scale.min(MAX_SCALE);
+
+        let value = if scale >= self.scale {
+            (self.value as i128).checked_mul(power_of_ten(scale - self.scale))?
+        } else {
+            rounding.divide(self.value as i128, power_of_ten(self.scale - scale))?
+        };
+
+        Some(Money::new(i64::try_from(value).ok()?, scale))
+    }
+
+    /// gives the amount with at least the given number of decimal places, or unchanged if it is too large to have them.
+    pub(crate) fn with_minimum_scale(&self, scale: u32) -> Self {
+        self.rescale(self.scale.max(scale), RoundingMode::HalfEven).unwrap_or(*self)
+    }
+
+    pub fn checked_add(&self, other: Money) -> Option<Self> {
+        let scale = self.scale.max(other.scale);
+
+        Some(Money::new(self.at_scale(scale)?.checked_add(other.at_scale(scale)?)?, scale))
+    }
+
+    pub fn checked_sub(&self, other: Money) -> Option<Self> {
+        self.checked_add(other.checked_neg()?)
+    }
+
+    pub fn checked_neg(&self) -> Option<Self> {
+        Some(Money::new(self.value.checked_neg()?, self.scale))
+    }
+
+    /// multiplies the amount by a factor, keeping the scale of this amount and rounding in the given mode.
+    pub fn checked_mul(&self, factor: Money, rounding: RoundingMode) -> Option<Self> {
+        self.multiply(factor, 0, rounding)
+    }
+
+    /// divides the amount by a divisor, keeping the scale of this amount and rounding in the given mode, giving None when dividing by zero.
+    pub fn checked_div(&self, divisor: Money, rounding: RoundingMode) -> Option<Self> {
+        let dividend = (self.value as i128).checked_mul(power_of_ten(divisor.scale))?;
+        let (dividend, divisor) = if divisor.value < 0 { (dividend.checked_neg()?, -(divisor.value as i128)) } else { (dividend, divisor.value as i128) };
+        let value = rounding.divide(dividend, divisor)?;
+
+        Some(Money::new(i64::try_from(value).ok()?, self.scale))
+    }
+
+    /// takes a percentage of the amount, e.g. 18.9 for 18.9%, keeping the scale of this amount and rounding in the given mode.
+    pub fn percentage(&self, percentage: Money, rounding: RoundingMode) -> Option<Self> {
+        self.multiply(percentage, 2, rounding)
+    }
+
+    /// multiplies by a factor that is divided by ten to the given power, and rounds back to this scale.
+    fn multiply(&self, factor: Money, extra_scale: u32, rounding: RoundingMode) -> Option<Self> {
+        let product = (self.value as i128).checked_mul(factor.value as i128)?;
+        let value = rounding.divide(product, power_of_ten(factor.scale + extra_scale))?;
+
+        Some(Money::new(i64::try_from(value).ok()?, self.scale))
+    }
+
+    /// retrieve the value at a larger scale, if it fits.
+    fn at_scale(&self, scale: u32) -> Option<i64> {
+        self.value.checked_mul(i64::try_from(power_of_ten(scale - self.scale)).ok()?)
+    }
+
+    /// the same amount without trailing zeros, so equal amounts look the same.
+    fn normalized(&self) -> (i64, u32) {
+        let (mut value, mut scale) = (self.value, self.scale);
+
+        while scale > 0 && value % 10 == 0 {
+            value /= 10;
+            scale -= 1;
+        }
+
+        (value, scale)
+    }
+}
+
+fn power_of_ten(exponent: u32) -> i128 {
+    10i128.pow(exponent)
+}
+
+impl PartialEq for Money {
+    fn eq(&self, other: &Self) -> bool {
+        self.normalized() == other.normalized()
+    }
+}
+
+impl Eq for Money {}
+
+impl Hash for Money {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.normalized().hash(state);
+    }
+}
+
+impl PartialOrd for Money {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Money {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let scale = self.scale.max(other.scale);
+        let value = |money: &Money| money.value as i128 * power_of_ten(scale - money.scale);
+
+        value(self).cmp(&value(other))
+    }
+}
+
+/// writes the amount with all of its decimal places, e.g. -20.50.
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digits = self.value.unsigned_abs().to_string();
+        let scale = self.scale as usize;
+        let sign = if self.value < 0 { "-" } else { "" };
+
+        if scale == 0 {
+            return write!(f, "{}{}", sign, digits)
+        }
+
+        let digits = format!("{:0>width$}", digits, width = scale + 1);
+        let (whole, fraction) = digits.split_at(digits.len() - scale);
+
+        write!(f, "{}{}.{}", sign, whole, fraction)
+    }
+}
\ No newline at end of file
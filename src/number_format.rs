@@ -0,0 +1,126 @@
+use crate::Money;
+
+/**
+ * structure that describes how amounts are written in a QIF file.
+ *
+ * The default matches files made in the United States,
+ * where amounts look like -1,234.56.
+ * Grouping separators, currency symbols, and parentheses
+ * are accepted when reading amounts,
+ * but only the decimal separator is used when writing them,
+ * since that is all Quicken expects.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct NumberFormat {
+    pub decimal_separator: char,
+    pub grouping_separator: Option<char>,
+    pub currency_symbols: Vec<String>,
+    pub parenthesized_negatives: bool
+}
+
+impl NumberFormat {
+    /**
+     * create a number format from an example of one thousand,
+     * such as 1,234.56, 1.234,56, 1 234,56, or 1'234.56.
+     */
+    pub fn from(s: &str) -> Option<Self> {
+        let (grouping_separator, decimal_separator) = match s {
+            "1,234.56" => (Some(','), '.'),
+            "1.234,56" => (Some('.'), ','),
+            "1 234,56" => (Some(' '), ','),
+            "1'234.56" => (Some('\''), '.'),
+            "1234.56" => (None, '.'),
+            "1234,56" => (None, ','),
+            _ => return None
+        };
+
+        Some(NumberFormat {
+            decimal_separator,
+            grouping_separator,
+            ..Self::default()
+        })
+    }
+
+    /// parses an amount, returning None if it is not a number in this format.
+    pub fn parse(&self, s: &str) -> Option<Money> {
+        let (number, is_negative) = self.normalize(s)?;
+
+        Money::from(&number).and_then(|amount| if is_negative { amount.checked_neg() } else { Some(amount) })
+    }
+
+    /**
+     * parses a value like a rate, returning None if it is not a number in this format.
+     * Values are read the same way as amounts, so text such as inf, NaN, or 1e5 is not a number.
+     */
+    pub fn parse_value(&self, s: &str) -> Option<f64> {
+        self.parse(s).map(|value| value.to_f64())
+    }
+
+    /**
+     * writes an amount with two decimal places, as amounts are written in QIF files,
+     * or with as many as it has if that is more, so amounts keep the precision they were read with.
+     */
+    pub fn format(&self, amount: Money) -> String {
+        self.use_decimal_separator(amount.with_minimum_scale(2).to_string())
+    }
+
+    /// writes a value like a price or quantity with exactly the decimal places it has.
+    pub fn format_exact(&self, value: Money) -> String {
+        self.use_decimal_separator(value.to_string())
+    }
+
+    /// writes a value like a rate, without rounding it.
+    pub fn format_value(&self, value: f64) -> String {
+        self.use_decimal_separator(format!("{}", value))
+    }
+
+    /// removes everything but the number itself, giving it with a '.' decimal separator and whether it was parenthesized.
+    fn normalize(&self, s: &str) -> Option<(String, bool)> {
+        let mut text = s.trim();
+        let mut is_negative = false;
+
+        if self.parenthesized_negatives && text.starts_with('(') && text.ends_with(')') {
+            text = text[1..text.len() - 1].trim();
+            is_negative = true;
+        }
+
+        let mut number = text.to_string();
+
+        for symbol in &self.currency_symbols {
+            number = number.replace(symbol.as_str(), "");
+        }
+
+        if let Some(grouping_separator) = self.grouping_separator {
+            number.retain(|character| character != grouping_separator && !(grouping_separator == ' ' && character.is_whitespace()));
+        }
+
+        let number: String = number.trim().chars()
+            .map(|character| if character == self.decimal_separator { '.' } else { character })
+            .collect();
+
+        if number.is_empty() {
+            None
+        } else {
+            Some((number, is_negative))
+        }
+    }
+
+    fn use_decimal_separator(&self, s: String) -> String {
+        if self.decimal_separator == '.' {
+            s
+        } else {
+            s.replace('.', &self.decimal_separator.to_string())
+        }
+    }
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        NumberFormat {
+            decimal_separator: '.',
+            grouping_separator: Some(','),
+            currency_symbols: ["$", "€", "£", "¥"].iter().map(|symbol| symbol.to_string()).collect(),
+            parenthesized_negatives: true
+        }
+    }
+}
\ No newline at end of file
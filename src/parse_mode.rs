@@ -0,0 +1,42 @@
+use crate::QifError;
+
+/**
+ * enumeration for how problems in a QIF file are handled when parsing it.
+ *
+ * In strict mode, the first date or amount that cannot be read,
+ * or header that is not understood, stops parsing with an error.
+ * In lenient mode, the problem is recorded as a warning instead,
+ * and parsing carries on, keeping what it can.
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ParseMode {
+    #[default]
+    Strict,
+    Lenient
+}
+
+impl ParseMode {
+    /**
+     * hands back the value of a result that worked.
+     * Otherwise, strict mode hands back the error,
+     * while lenient mode adds it to the warnings and hands back nothing.
+     */
+    pub(crate) fn tolerate<T>(&self, result: Result<T, QifError>, warnings: &mut Vec<QifError>) -> Result<Option<T>, QifError> {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(error) => self.report(error, warnings).map(|_| None)
+        }
+    }
+
+    /// hands back the error in strict mode, or adds it to the warnings in lenient mode.
+    pub(crate) fn report(&self, error: QifError, warnings: &mut Vec<QifError>) -> Result<(), QifError> {
+        match self {
+            Self::Strict => Err(error),
+            Self::Lenient => {
+                warnings.push(error);
+
+                Ok(())
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,53 @@
+use crate::{DateFormat, NumberFormat, ParseMode, MergePolicy};
+
+/**
+ * structure that holds the settings used to parse a QIF file.
+ *
+ * Parsing with options hands back the warnings found along with the result,
+ * which are the errors that lenient mode carried on past:
+ *
+ * ```ignore
+ * let options = ParseOptions::from(&DateFormat::MonthDayFullYear, &NumberFormat::default(), ParseMode::Lenient);
+ * let (qif, warnings) = QIF::from_str_with(&text, &options)?;
+ * ```
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseOptions {
+    pub date_format: DateFormat,
+    pub number_format: NumberFormat,
+    pub mode: ParseMode,
+    /// how records are merged when a file has more than one section of the same type, which keeps every record by default.
+    pub merge_policy: MergePolicy
+}
+
+impl ParseOptions {
+    pub fn from(date_format: &DateFormat, number_format: &NumberFormat, mode: ParseMode) -> Self {
+        ParseOptions {
+            date_format: date_format.clone(),
+            number_format: number_format.clone(),
+            mode,
+            merge_policy: MergePolicy::default()
+        }
+    }
+
+    /// options for parsing strictly, which is what parsing without options does.
+    pub fn strict(date_format: &DateFormat, number_format: &NumberFormat) -> Self {
+        Self::from(date_format, number_format, ParseMode::Strict)
+    }
+
+    pub fn lenient(date_format: &DateFormat, number_format: &NumberFormat) -> Self {
+        Self::from(date_format, number_format, ParseMode::Lenient)
+    }
+
+    /// changes how records are merged, so duplicates can be dropped when reading a file.
+    pub fn with_merge_policy(mut self, merge_policy: MergePolicy) -> Self {
+        self.merge_policy = merge_policy;
+        self
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::strict(&DateFormat::MonthDayFullYear, &NumberFormat::default())
+    }
+}
\ No newline at end of file
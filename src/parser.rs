@@ -0,0 +1,136 @@
+use crate::Header;
+
+/// enumeration for the kinds of lines in a QIF file.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Line<'a> {
+    Header(Header),
+    /// a field, made of its code (e.g. T for the amount) and the rest of the line.
+    Field(char, &'a str),
+    /// the ^ that ends a record.
+    End,
+    Blank
+}
+
+impl<'a> Line<'a> {
+    pub(crate) fn from(line: &'a str) -> Self {
+        if line.trim().is_empty() {
+            return Self::Blank
+        }
+
+        if let Some(header) = Header::from(line) {
+            return Self::Header(header)
+        }
+
+        let mut characters = line.chars();
+
+        match characters.next() {
+            Some('^') => Self::End,
+            Some(code) => Self::Field(code, characters.as_str()),
+            None => Self::Blank
+        }
+    }
+}
+
+/**
+ * enumeration for what the parser finds in a QIF file,
+ * along with the line it starts on, counting from 1.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event<L> {
+    Header(usize, Header),
+    /**
+     * the lines of a record, without the ^ that ended it.
+     * Blank lines within the record are kept, so the line of each field can be worked out.
+     */
+    Record(usize, Vec<L>)
+}
+
+/**
+ * structure that reads a QIF file line by line,
+ * handing back headers and complete records as it finds them.
+ *
+ * Since each line is looked at on its own,
+ * a ^ inside of a memo or payee does not end a record.
+ * A record that is cut off by a header or the end of the file
+ * is still handed back.
+ *
+ * The parser works with anything that gives lines,
+ * so the lines of a string can be parsed like this:
+ *
+ * ```ignore
+ * for event in Parser::new(text.lines()) {
+ *     println!("{:?}", event);
+ * }
+ * ```
+ */
+pub struct Parser<I: Iterator> {
+    lines: I,
+    line_number: usize,
+    record_start: usize,
+    fields: Vec<I::Item>,
+    next_header: Option<(usize, Header)>
+}
+
+impl<I> Parser<I> where I: Iterator, I::Item: AsRef<str> {
+    pub fn new(lines: I) -> Self {
+        Parser {
+            lines,
+            line_number: 0,
+            record_start: 0,
+            fields: vec![],
+            next_header: None
+        }
+    }
+
+    /// retrieve the lines being parsed, e.g. to check a reader for errors.
+    pub(crate) fn lines_mut(&mut self) -> &mut I {
+        &mut self.lines
+    }
+
+    fn take_record(&mut self) -> Option<Event<I::Item>> {
+        if self.fields.is_empty() {
+            None
+        } else {
+            Some(Event::Record(self.record_start, std::mem::take(&mut self.fields)))
+        }
+    }
+}
+
+impl<I> Iterator for Parser<I> where I: Iterator, I::Item: AsRef<str> {
+    type Item = Event<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((line_number, header)) = self.next_header.take() {
+            return Some(Event::Header(line_number, header))
+        }
+
+        while let Some(line) = self.lines.next() {
+            self.line_number += 1;
+
+            match Line::from(line.as_ref()) {
+                Line::Header(header) => if let Some(record) = self.take_record() {
+                    self.next_header = Some((self.line_number, header));
+
+                    return Some(record)
+                } else {
+                    return Some(Event::Header(self.line_number, header))
+                },
+                Line::End => if let Some(record) = self.take_record() {
+                    return Some(record)
+                },
+                Line::Field(_, _) => {
+                    if self.fields.is_empty() {
+                        self.record_start = self.line_number;
+                    }
+
+                    self.fields.push(line)
+                },
+                Line::Blank => if !self.fields.is_empty() {
+                    self.fields.push(line)
+                }
+            }
+        }
+
+        self.take_record()
+    }
+}
\ No newline at end of file
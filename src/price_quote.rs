@@ -0,0 +1,73 @@
+use chrono::prelude::*;
+
+use crate::{DateFormat, NumberFormat, QifError, Money};
+use crate::transaction::parse_date;
+
+use std::fmt;
+
+/**
+ * structure that represents a line in the price history of a QIF file,
+ * which looks like "SYMBOL",price,"date".
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct PriceQuote {
+    pub symbol: String,
+    pub price: Money,
+    pub date: DateTime<Local>
+}
+
+impl PriceQuote {
+    /// create a price quote from its respective parts.
+    pub fn from(symbol: &str, price: Money, date: DateTime<Local>) -> Self {
+        PriceQuote {
+            symbol: symbol.to_string(),
+            price,
+            date
+        }
+    }
+
+    /**
+     * writes the price line, keeping the decimal places the price was read with.
+     * A price written with a decimal comma is quoted, so it is not taken for another part.
+     */
+    pub fn to_string(&self, df: &DateFormat, nf: &NumberFormat) -> String {
+        let price = nf.format_exact(self.price);
+
+        format!("\"{}\",{},\"{}\"",
+            self.symbol,
+            if price.contains(',') { format!("\"{}\"", price) } else { price },
+            self.date.format(df.chrono_str()))
+    }
+
+    /**
+     * parses a single price line.
+     * A missing part is reported as a missing field,
+     * using S for the symbol, P for the price, and D for the date.
+     * Everything between the symbol and the date is the price,
+     * so prices with grouping separators or decimal commas can be read.
+     */
+    pub fn from_str(s: &str, df: &DateFormat, nf: &NumberFormat) -> Result<Self, QifError> {
+        let text = s.trim();
+        let (symbol, rest) = text.split_once(',').unwrap_or((text, ""));
+        let (price, date) = rest.rsplit_once(',').unwrap_or((rest, ""));
+
+        let symbol = read_part(symbol, 'S', s)?;
+        let price = nf.parse(read_part(price, 'P', s)?).ok_or_else(|| QifError::bad_amount(s))?;
+        let date = parse_date(read_part(date, 'D', s)?, df).ok_or_else(|| QifError::bad_date(s))?;
+
+        Ok(PriceQuote::from(symbol, price, date))
+    }
+}
+
+/// removes the quotes around a part of a price line, reporting the field as missing if nothing is left.
+fn read_part<'a>(part: &'a str, field: char, line: &str) -> Result<&'a str, QifError> {
+    Some(part.trim().trim_matches('"'))
+        .filter(|part| !part.is_empty())
+        .ok_or_else(|| QifError::missing_field(field, &[line]))
+}
+
+impl fmt::Display for PriceQuote {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_string(&DateFormat::MonthDayFullYear, &NumberFormat::default()))
+    }
+}
\ No newline at end of file
@@ -1,15 +1,20 @@
-use std::{fmt, io::{self, Read, Write }, fs::File};
+use std::{fmt, collections::VecDeque, io::{self, BufReader, BufWriter}, fs::File};
 
-use crate::{ Section, Type, Transaction, DateFormat };
+use crate::{ QifItem, Section, Type, Header, Parser, Event, Record, QifReader, QifWriter, Account, Category, Class, Security, PriceQuote, DateFormat, NumberFormat, QifError, ParseOptions, Currency, CurrencyAmount, CurrencyError, RateTable, RoundingMode, MergePolicy };
+use crate::reader::Position;
+use crate::writer::write_to_string;
 
-/// A structure that represents a QIF document.
-#[derive(Debug, PartialEq)]
+/**
+ * A structure that represents a QIF document.
+ * 
+ * The sections, lists, and headers of the file are kept as items in the order they appear,
+ * so writing a document that was read keeps its layout,
+ * and files with several sections or accounts of the same type keep each apart.
+ * Lookups like sections_of and categories find what is wanted among the items.
+ */
+#[derive(Clone, Debug, PartialEq)]
 pub struct QIF {
-    pub cash: Option<Section>,
-    pub bank: Option<Section>,
-    pub credit_card: Option<Section>,
-    pub liability: Option<Section>,
-    pub asset: Option<Section>
+    pub items: Vec<QifItem>
 }
 
 impl QIF {
@@ -18,226 +23,478 @@ impl QIF {
      * 
      * Objects can be created like this:
      * 
+     * ```ignore
      * let expected_qif = QIF::builder()
-        .set_field(expected_section)
-        .build();
+     *     .add_category(salary)
+     *     .add_section(expected_section)
+     *     .build();
+     * ```
      */
     pub fn builder() -> QIFBuilder {
         QIFBuilder::new()
     }
 
-    pub fn to_string(&self, df: &DateFormat) -> String {
-        let mut content = String::default();
+    pub fn to_string(&self, df: &DateFormat, nf: &NumberFormat) -> String {
+        write_to_string(df, nf, |writer| writer.write_qif(self))
+    }
 
-        if self.cash.is_some() {
-            content.push_str(&self.field_to_string(Type::Cash, df));
-        }
-        
-        if self.bank.is_some() {
-            content.push_str(&self.field_to_string(Type::Bank, df));
-        }
+    pub fn from_str(s: &str, df: &DateFormat, nf: &NumberFormat) -> Result<QIF, QifError> {
+        Self::from_str_with(s, &ParseOptions::strict(df, nf)).map(|(qif, _)| qif)
+    }
 
-        if self.credit_card.is_some() {
-            content.push_str(&self.field_to_string(Type::CreditCard, df));
-        }
-        
-        if self.liability.is_some() {
-            content.push_str(&self.field_to_string(Type::Liability, df));
+    /**
+     * parses a QIF document with the given options, handing back the warnings found along with it.
+     * In lenient mode, transactions with a bad or missing date, payee, or amount are kept with defaults,
+     * other records that cannot be kept are left out,
+     * and headers that are not understood are kept by the section they are in.
+     */
+    pub fn from_str_with(s: &str, options: &ParseOptions) -> Result<(QIF, Vec<QifError>), QifError> {
+        Self::from_events_with(Parser::new(s.lines()), options)
+    }
+
+    /**
+     * builds a QIF document from the headers and records found by a Parser.
+     * Each header decides what the records that follow it are,
+     * until the next header comes along.
+     * Records are counted from 0 across the whole document, so errors can say which one they were found in.
+     */
+    pub fn from_events<L: AsRef<str>>(events: impl Iterator<Item = Event<L>>, df: &DateFormat, nf: &NumberFormat) -> Result<QIF, QifError> {
+        Self::from_events_with(events, &ParseOptions::strict(df, nf)).map(|(qif, _)| qif)
+    }
+
+    /// builds a QIF document from a Parser with the given options, handing back the warnings found along with it.
+    pub fn from_events_with<L: AsRef<str>>(events: impl Iterator<Item = Event<L>>, options: &ParseOptions) -> Result<(QIF, Vec<QifError>), QifError> {
+        let mut builder = QIF::builder();
+        let mut document = Document::merging_with(&options.merge_policy);
+        let mut position = Position::default();
+        let mut records = VecDeque::new();
+        let mut warnings = vec![];
+
+        for event in events {
+            position.read(event, options, &mut warnings, &mut records)?;
+
+            for record in records.drain(..) {
+                document.add(record, &mut builder);
+            }
         }
-        
-        if self.asset.is_some() {
-            content.push_str(&self.field_to_string(Type::Asset, df));
+
+        position.finish(&mut records);
+
+        for record in records.drain(..) {
+            document.add(record, &mut builder);
         }
 
-        content
+        document.finish(&mut builder);
+
+        Ok((builder.build(), warnings))
+    }
+
+    /**
+     * builds a QIF document from records, such as those read by a QifReader,
+     * stopping at the first error.
+     */
+    pub fn from_records(records: impl Iterator<Item = Result<Record, QifError>>) -> Result<QIF, QifError> {
+        Self::from_records_with(records, &MergePolicy::default())
     }
 
-    pub fn from_str(s: &str, df: &DateFormat) -> QIF {
+    /// builds a QIF document from records, dropping duplicates as the merge policy says.
+    pub fn from_records_with(records: impl Iterator<Item = Result<Record, QifError>>, merge_policy: &MergePolicy) -> Result<QIF, QifError> {
         let mut builder = QIF::builder();
+        let mut document = Document::merging_with(merge_policy);
 
-        let blocks: Vec<&str> = s.split("^").collect();
+        for record in records {
+            document.add(record?, &mut builder);
+        }
 
-        let mut current_section: Option<Section> = None;
+        document.finish(&mut builder);
 
-        for block in blocks {
-            if let Some(section) = Section::from_str(&block, df) {
-                if !builder.update_field(section.clone()) {
-                    builder.set_field(section.clone());
-                }
+        Ok(builder.build())
+    }
 
-                current_section = match section.qif_type {
-                    Type::Cash => builder.cash.clone(),
-                    Type::Bank => builder.bank.clone(),
-                    Type::CreditCard => builder.credit_card.clone(),
-                    Type::Liability => builder.liability.clone(),
-                    Type::Asset => builder.asset.clone(),
-                };
-            } else if let Ok(transaction) = Transaction::from_str(&block, df) {
-                if let Some(mut current) = current_section.clone() {
-                    current.add_transaction_if_not_exists(&transaction);
-
-                    builder.update_field(current);
-                }
-            }
-        }
+    /**
+     * parses a QIF document without knowing its date format,
+     * using the format that DateFormat::detect finds.
+     * If the dates could be read more than one way, month first formats are preferred,
+     * and if no dates are found, mm/dd/yyyy is assumed.
+     */
+    pub fn from_str_auto(s: &str, nf: &NumberFormat) -> Result<QIF, QifError> {
+        let format = DateFormat::detect(s).format.unwrap_or(DateFormat::MonthDayFullYear);
+
+        Self::from_str(s, &format, nf)
+    }
+
+    /// retrieve every section, in the order they appear.
+    pub fn sections(&self) -> impl Iterator<Item = &Section> {
+        self.items.iter().filter_map(|item| item.as_section())
+    }
+
+    pub fn sections_mut(&mut self) -> impl Iterator<Item = &mut Section> {
+        self.items.iter_mut().filter_map(|item| item.as_section_mut())
+    }
 
-        builder.build()
+    /// retrieve the sections of the given type, whether or not they belong to an account.
+    pub fn sections_of(&self, qif_type: Type) -> impl Iterator<Item = &Section> {
+        self.sections().filter(move |section| section.qif_type == qif_type)
     }
 
-    fn field_to_string(&self, field: Type, df: &DateFormat) -> String {
-        match  field {
-            Type::Cash => if let Some(cash) = self.cash.clone() {
-                cash.to_string(df)
-            } else {
-                String::default()
+    /// retrieve the first section of the given type that does not belong to an account.
+    pub fn section(&self, qif_type: Type) -> Option<&Section> {
+        self.sections_of(qif_type).find(|section| section.account.is_none())
+    }
+
+    /// retrieve the sections that belong to an account, in the order they appear.
+    pub fn account_sections(&self) -> impl Iterator<Item = &Section> {
+        self.sections().filter(|section| section.account.is_some())
+    }
+
+    /// retrieve the section that belongs to the account with the given name.
+    pub fn account_section(&self, name: &str) -> Option<&Section> {
+        self.account_sections().find(|section| {
+            section.account.as_ref().is_some_and(|account| account.name == name)
+        })
+    }
+
+    /// retrieve the accounts of every account list.
+    pub fn accounts(&self) -> impl Iterator<Item = &Account> {
+        self.items.iter().flat_map(|item| match item {
+            QifItem::Accounts(accounts) => accounts.as_slice(),
+            _ => &[]
+        })
+    }
+
+    pub fn categories(&self) -> impl Iterator<Item = &Category> {
+        self.items.iter().flat_map(|item| match item {
+            QifItem::Categories(categories) => categories.as_slice(),
+            _ => &[]
+        })
+    }
+
+    pub fn classes(&self) -> impl Iterator<Item = &Class> {
+        self.items.iter().flat_map(|item| match item {
+            QifItem::Classes(classes) => classes.as_slice(),
+            _ => &[]
+        })
+    }
+
+    pub fn securities(&self) -> impl Iterator<Item = &Security> {
+        self.items.iter().flat_map(|item| match item {
+            QifItem::Securities(securities) => securities.as_slice(),
+            _ => &[]
+        })
+    }
+
+    pub fn prices(&self) -> impl Iterator<Item = &PriceQuote> {
+        self.items.iter().flat_map(|item| match item {
+            QifItem::Prices(prices) => prices.as_slice(),
+            _ => &[]
+        })
+    }
+
+    /**
+     * adds up the regular transactions in every section that has any, converted into the given currency with the given rates,
+     * so accounts kept in several currencies can be reported in one.
+     * Each of those sections needs a currency, either of its own or from its account.
+     */
+    pub fn balance_in(&self, currency: Currency, rates: &RateTable, rounding: RoundingMode) -> Result<CurrencyAmount, CurrencyError> {
+        self.sections()
+            .filter(|section| !section.transactions.is_empty())
+            .try_fold(CurrencyAmount::zero(currency), |balance, section| {
+                balance.checked_add(section.balance_in(currency, rates, rounding)?)
+            })
+    }
+
+    pub fn load_from_file(p: &str, df: &DateFormat, nf: &NumberFormat) -> Result<Self, QifError> {
+        Self::load_from_file_with(p, &ParseOptions::strict(df, nf)).map(|(qif, _)| qif)
+    }
+
+    /// reads a file a record at a time with the given options, handing back the warnings found along with the document.
+    pub fn load_from_file_with(p: &str, options: &ParseOptions) -> Result<(Self, Vec<QifError>), QifError> {
+        let mut reader = QifReader::new(BufReader::new(File::open(p)?), options);
+        let qif = Self::from_records_with(&mut reader, &options.merge_policy)?;
+
+        Ok((qif, reader.take_warnings()))
+    }
+
+    /// writes the document to a file a record at a time, without building it up as a string first.
+    pub fn save(&self, p: &str, df: &DateFormat, nf: &NumberFormat) -> Result<(), io::Error> {
+        let mut writer = QifWriter::new(BufWriter::new(File::create(p)?), df, nf);
+
+        writer.write_qif(self)?;
+        writer.finish()?;
+
+        Ok(())
+    }
+}
+
+/**
+ * structure that puts records into a QIF document as they are read,
+ * keeping track of whether transactions have a section to go into.
+ * Headers that are not understood are held until a section can keep them,
+ * or kept as items of their own if a list or the end of the file comes first.
+ */
+#[derive(Default)]
+struct Document {
+    merge_policy: MergePolicy,
+    in_section: bool,
+    headers: Vec<Header>
+}
+
+impl Document {
+    fn merging_with(merge_policy: &MergePolicy) -> Self {
+        Document { merge_policy: *merge_policy, ..Document::default() }
+    }
+
+    fn add(&mut self, record: Record, builder: &mut QIFBuilder) {
+        match record {
+            Record::SectionStart(qif_type) => if let Some(section) = self.start_section(&qif_type) {
+                builder.add_section(section);
+                self.in_section = true;
             },
-            Type::Bank => if let Some(bank) = self.bank.clone() {
-                bank.to_string(df)
-            } else {
-                String::default()
+            Record::AccountStart(account, qif_type) => if let Some(mut section) = self.start_section(&qif_type) {
+                section.account = Some(account);
+                builder.add_account_section(section);
+                self.in_section = true;
             },
-            Type::CreditCard => if let Some(credit_card) = self.credit_card.clone() {
-                credit_card.to_string(df)
-            } else {
-                String::default()
+            Record::Header(header) => self.headers.push(header),
+            Record::Account(account) => {
+                self.end_section(builder);
+                builder.add_account(account);
             },
-            Type::Liability => if let Some(liability) = self.liability.clone() {
-                liability.to_string(df)
-            } else {
-                String::default()
+            Record::Category(category) => {
+                self.end_section(builder);
+                builder.append_category(category);
             },
-            Type::Asset => if let Some(asset) = self.asset.clone() {
-                asset.to_string(df)
-            } else {
-                String::default()
+            Record::Class(class) => {
+                self.end_section(builder);
+                builder.add_class(class);
             },
+            Record::Security(security) => {
+                self.end_section(builder);
+                builder.add_security(security);
+            },
+            Record::Price(price) => {
+                self.end_section(builder);
+                builder.add_price(price);
+            },
+            Record::Unknown(lines) => {
+                self.end_section(builder);
+                builder.add_unknown(lines);
+            },
+            record => {
+                let is_kept = self.is_kept(&record, builder);
+
+                if let Some(section) = self.current_section(builder) {
+                    for header in self.headers.drain(..) {
+                        section.add_inner_header(&header);
+                    }
+
+                    if is_kept {
+                        section.push(record);
+                    }
+                }
+            }
         }
     }
 
-    pub fn load_from_file(p: &str, df: &DateFormat) -> Result<Self, String> {
-        match file_contents_from(p) {
-            Ok(content) => Ok(Self::from_str(&content, df)),
-            Err(error) => Err(format!("{}", error))
+    /// creates an empty section of the given type, which keeps any headers being held.
+    fn start_section(&mut self, qif_type: &Type) -> Option<Section> {
+        let mut section = Section::builder().set_type(qif_type.to_str()).build()?;
+
+        for header in self.headers.drain(..) {
+            section.add_header(&header);
         }
-    }
 
-    pub fn save(&self, p: &str, df: &DateFormat) -> Result<(), io::Error> {
-        let mut output = File::create(p)?;
+        Some(section)
+    }
 
-        match write!(output, "{}", format!("{}", self.to_string(df))) {
-            Ok(()) => Ok(()),
-            Err(error) => Err(error)
+    /// keeps any headers being held as items, since what follows is not a section.
+    fn end_section(&mut self, builder: &mut QIFBuilder) {
+        for header in self.headers.drain(..) {
+            builder.add_header(header);
         }
+
+        self.in_section = false;
     }
-}
 
-fn file_contents_from(f: &str) -> Result<String, io::Error> {
-    let mut file_contents = String::default();
-    File::open(f)?.read_to_string(&mut file_contents)?;
+    fn finish(&mut self, builder: &mut QIFBuilder) {
+        self.end_section(builder);
+    }
 
-    Ok(file_contents)
+    /**
+     * checks if the merge policy keeps a record going into the current section,
+     * comparing it with the records of every section of the same type and account.
+     */
+    fn is_kept(&self, record: &Record, builder: &QIFBuilder) -> bool {
+        let current = match builder.items.last().and_then(|item| item.as_section()) {
+            Some(current) if self.in_section => current,
+            _ => return false
+        };
+
+        builder.items.iter()
+            .filter_map(|item| item.as_section())
+            .filter(|section| section.qif_type == current.qif_type && section.account == current.account)
+            .all(|section| section.keeps(record, &self.merge_policy))
+    }
+
+    fn current_section<'a>(&self, builder: &'a mut QIFBuilder) -> Option<&'a mut Section> {
+        if self.in_section {
+            builder.items.last_mut().and_then(|item| item.as_section_mut())
+        } else {
+            None
+        }
+    }
 }
 
 impl fmt::Display for QIF {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.to_string(&DateFormat::MonthDayFullYear))
+        write!(f, "{}", self.to_string(&DateFormat::MonthDayFullYear, &NumberFormat::default()))
     }
 }
 
+/**
+ * structure that builds a QIF document an item at a time.
+ * Entries of a list that are added one after another are kept in a single list.
+ */
 pub struct QIFBuilder {
-    pub cash: Option<Section>,
-    pub bank: Option<Section>,
-    pub credit_card: Option<Section>,
-    pub liability: Option<Section>,
-    pub asset: Option<Section>
+    pub items: Vec<QifItem>
+}
+
+impl Default for QIFBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl QIFBuilder {
     pub fn new() -> Self {
         QIFBuilder { 
-            cash: None, 
-            bank: None, 
-            credit_card: None, 
-            liability: None, 
-            asset: None 
+            items: vec![]
         }
     }
 
+    /**
+     * sets the section of its type that does not belong to an account,
+     * replacing one that was already set where it is, or adding it after everything else.
+     */
     pub fn set_field(&mut self, section: Section) -> &mut Self {
-        match section.qif_type {
-            Type::Cash => self.cash = Some(section.clone()),
-            Type::Bank => self.bank = Some(section.clone()),
-            Type::CreditCard => self.credit_card = Some(section.clone()),
-            Type::Liability => self.liability = Some(section.clone()),
-            Type::Asset => self.asset = Some(section.clone())
+        match self.field_mut(&section.qif_type) {
+            Some(existing) => *existing = section,
+            None => self.items.push(QifItem::Section(section))
         }
         self
     }
 
+    /// retrieve the first section of the given type that does not belong to an account, so records can be added to it in place.
+    pub fn field_mut(&mut self, field: &Type) -> Option<&mut Section> {
+        self.items.iter_mut()
+            .filter_map(|item| item.as_section_mut())
+            .find(|section| &section.qif_type == field && section.account.is_none())
+    }
+
+    /// adds a section after everything already added, even if there is already one of its type.
+    pub fn add_section(&mut self, section: Section) -> &mut Self {
+        self.items.push(QifItem::Section(section));
+        self
+    }
+
+    /// adds a section that belongs to a named account, after everything already added.
+    pub fn add_account_section(&mut self, section: Section) -> &mut Self {
+        self.add_section(section)
+    }
+
+    /**
+     * adds a category in front of the first section, if there is one,
+     * so it is written before the account sections as Quicken expects.
+     */
+    pub fn add_category(&mut self, category: Category) -> &mut Self {
+        let first_section = self.items.iter().position(|item| item.as_section().is_some()).unwrap_or(self.items.len());
+
+        match self.items[..first_section].last_mut() {
+            Some(QifItem::Categories(categories)) => categories.push(category),
+            _ => self.items.insert(first_section, QifItem::Categories(vec![category]))
+        }
+        self
+    }
+
+    /// adds a category after everything already added, where it was read.
+    pub(crate) fn append_category(&mut self, category: Category) -> &mut Self {
+        self.add_to_list(category, |item| match item {
+            QifItem::Categories(categories) => Some(categories),
+            _ => None
+        }, QifItem::Categories)
+    }
+
+    pub fn add_class(&mut self, class: Class) -> &mut Self {
+        self.add_to_list(class, |item| match item {
+            QifItem::Classes(classes) => Some(classes),
+            _ => None
+        }, QifItem::Classes)
+    }
+
+    pub fn add_account(&mut self, account: Account) -> &mut Self {
+        self.add_to_list(account, |item| match item {
+            QifItem::Accounts(accounts) => Some(accounts),
+            _ => None
+        }, QifItem::Accounts)
+    }
+
+    pub fn add_security(&mut self, security: Security) -> &mut Self {
+        self.add_to_list(security, |item| match item {
+            QifItem::Securities(securities) => Some(securities),
+            _ => None
+        }, QifItem::Securities)
+    }
+
+    pub fn add_price(&mut self, price: PriceQuote) -> &mut Self {
+        self.add_to_list(price, |item| match item {
+            QifItem::Prices(prices) => Some(prices),
+            _ => None
+        }, QifItem::Prices)
+    }
+
+    /// adds a header that is not part of a section or list, such as an option.
+    pub fn add_header(&mut self, header: Header) -> &mut Self {
+        self.items.push(QifItem::Header(header));
+        self
+    }
+
+    /// adds the lines of a record that is not part of a section or list, to be written back as they are.
+    pub fn add_unknown(&mut self, lines: Vec<String>) -> &mut Self {
+        self.items.push(QifItem::Unknown(lines));
+        self
+    }
+
     pub fn build(&self) -> QIF {
         QIF { 
-            cash: self.cash.clone(), 
-            bank: self.bank.clone(), 
-            credit_card: self.credit_card.clone(), 
-            liability: self.liability.clone(), 
-            asset: self.asset.clone() 
+            items: self.items.clone()
         }
     }
 
+    /**
+     * adds the records of a section to the first section of the same type that does not belong to an account, if there is one,
+     * keeping every record, and returns whether there was a section to add them to.
+     */
     pub fn update_field(&mut self, section: Section) -> bool {
-        let mut was_updated = false;
-        match section.qif_type {
-            Type::Cash => {
-                if let Some(mut cash) = self.cash.clone() {
-                    for transaction in &section.transactions {
-                       cash.add_transaction_if_not_exists(transaction) 
-                    }
-
-                    self.set_field(cash);
-                    was_updated = true; 
-                }
-            },
-            Type::Bank => {
-                if let Some(mut bank) = self.bank.clone() {
-                    for transaction in &section.transactions {
-                       bank.add_transaction_if_not_exists(transaction) 
-                    }
+        self.update_field_with(section, &MergePolicy::default())
+    }
 
-                    self.set_field(bank);
-                    was_updated = true
-                }
-            },
-            Type::CreditCard => {
-                if let Some(mut credit_card) = self.credit_card.clone() {
-                    for transaction in &section.transactions {
-                       credit_card.add_transaction_if_not_exists(transaction) 
-                    }
+    /// adds the records of a section to the first section of the same type, if there is one, dropping duplicates as the merge policy says.
+    pub fn update_field_with(&mut self, section: Section, merge_policy: &MergePolicy) -> bool {
+        if let Some(existing) = self.field_mut(&section.qif_type) {
+            existing.merge(section, merge_policy);
 
-                    self.set_field(credit_card);
-                    was_updated = true
-                }
-            },
-            Type::Liability => {
-                if let Some(mut liability) = self.liability.clone() {
-                    for transaction in &section.transactions {
-                        liability.add_transaction_if_not_exists(transaction) 
-                    }
-
-                    self.set_field(liability);
-                    was_updated = true
-                }
-            },
-            Type::Asset => {
-                if let Some(mut asset) = self.asset.clone() {
-                    for transaction in &section.transactions {
-                       asset.add_transaction_if_not_exists(transaction) 
-                    }
+            true
+        } else {
+            false
+        }
+    }
 
-                    self.set_field(asset);
-                    was_updated = true
-                }
-            },
+    /// adds an entry to the list that was added last, if it is the same kind of list, or else starts a new list.
+    fn add_to_list<T>(&mut self, entry: T, list_of: impl Fn(&mut QifItem) -> Option<&mut Vec<T>>, new_list: impl Fn(Vec<T>) -> QifItem) -> &mut Self {
+        match self.items.last_mut().and_then(list_of) {
+            Some(list) => list.push(entry),
+            None => self.items.push(new_list(vec![entry]))
         }
-        return was_updated;
+        self
     }
 }
\ No newline at end of file
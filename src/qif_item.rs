@@ -0,0 +1,41 @@
+use crate::{Section, Header, Account, Category, Class, Security, PriceQuote};
+
+/**
+ * enumeration for the parts of a QIF document, in the order they are found in the file.
+ *
+ * Entries of a list that follow one another are kept together,
+ * so a category list is a single item, however many categories it has.
+ */
+#[derive(Clone, Debug, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum QifItem {
+    /// a section of transactions, which belongs to an account if it follows an !Account header.
+    Section(Section),
+    /// an account list, which is read while !Option:AutoSwitch is on, or holds accounts that no section follows.
+    Accounts(Vec<Account>),
+    Categories(Vec<Category>),
+    Classes(Vec<Class>),
+    Securities(Vec<Security>),
+    Prices(Vec<PriceQuote>),
+    /// a header that is not part of a section or list, such as an option at the end of the file.
+    Header(Header),
+    /// the lines of a record found before any header, or after a header that is not known, kept as they were written.
+    Unknown(Vec<String>)
+}
+
+impl QifItem {
+    /// retrieve the section, if this item is one.
+    pub fn as_section(&self) -> Option<&Section> {
+        match self {
+            Self::Section(section) => Some(section),
+            _ => None
+        }
+    }
+
+    pub fn as_section_mut(&mut self) -> Option<&mut Section> {
+        match self {
+            Self::Section(section) => Some(section),
+            _ => None
+        }
+    }
+}
\ No newline at end of file
@@ -1,24 +1,34 @@
-/**
- * enumeration for specifying qif type.
- * Only noninvoice and noninvestment times are currently present.
- */
+/// enumeration for specifying qif type.
 #[derive(Clone, Debug, PartialEq)]
 pub enum QIFType {
     Cash,
     Bank,
     CreditCard,
     Liability,
-    Asset
+    Asset,
+    Investment,
+    Memorized,
+    Invoice,
+    /// a type this library does not know, such as the Port or 401(k)/403(b) account types, kept as it was written.
+    Other(String)
 }
 
 impl QIFType {
+    /**
+     * reads a type without regard to case or extra whitespace, e.g. "oth  l" is Liability.
+     * Types that are not known are not read, as they cannot start a section;
+     * use `Other` to keep them, as accounts do.
+     */
     pub fn from(s: &str) -> Option<Self> {
-        match s.to_string() {
-            content if content == "Cash" => Some(Self::Cash),
-            content if content == "Bank" => Some(Self::Bank),
-            content if content == "CCard" => Some(Self::CreditCard),
-            content if content == "Oth L" => Some(Self::Liability),
-            content if content == "Oth A" => Some(Self::Asset),
+        match s.split_whitespace().collect::<Vec<&str>>().join(" ").to_lowercase() {
+            content if content == "cash" => Some(Self::Cash),
+            content if content == "bank" => Some(Self::Bank),
+            content if content == "ccard" => Some(Self::CreditCard),
+            content if content == "oth l" => Some(Self::Liability),
+            content if content == "oth a" => Some(Self::Asset),
+            content if content == "invst" => Some(Self::Investment),
+            content if content == "memorized" => Some(Self::Memorized),
+            content if content == "invoice" => Some(Self::Invoice),
             _ => None
         }
     }
@@ -29,7 +39,11 @@ impl QIFType {
             Self::Bank => "Bank",
             Self::CreditCard => "CCard",
             Self::Liability => "Oth L",
-            Self::Asset => "Oth A"
+            Self::Asset => "Oth A",
+            Self::Investment => "Invst",
+            Self::Memorized => "Memorized",
+            Self::Invoice => "Invoice",
+            Self::Other(name) => name
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+
+use crate::{Currency, CurrencyAmount, CurrencyError, ExchangeRate, RoundingMode};
+
+/**
+ * structure that holds the exchange rates used to report amounts in a single currency.
+ *
+ * The rates are supplied by the user, as QIF files do not carry them,
+ * and a rate can be used in either direction,
+ * so a rate from GBP to EUR also converts EUR to GBP.
+ *
+ * ```ignore
+ * let mut rates = RateTable::new();
+ *
+ * rates.add(ExchangeRate::new(Currency::from("GBP").unwrap(), Currency::from("EUR").unwrap(), Money::from("1.17").unwrap()))
+ *     .add(ExchangeRate::new(Currency::from("CAD").unwrap(), Currency::from("EUR").unwrap(), Money::from("0.68").unwrap()));
+ *
+ * let balance = qif.balance_in(Currency::from("EUR").unwrap(), &rates, RoundingMode::HalfEven)?;
+ * ```
+ */
+#[derive(Clone, Debug, Default)]
+pub struct RateTable {
+    rates: HashMap<(Currency, Currency), ExchangeRate>
+}
+
+impl RateTable {
+    pub fn new() -> Self {
+        RateTable { rates: HashMap::new() }
+    }
+
+    /// adds a rate, replacing any rate already given between the same currencies in the same direction.
+    pub fn add(&mut self, rate: ExchangeRate) -> &mut Self {
+        self.rates.insert((rate.from, rate.to), rate);
+        self
+    }
+
+    /// retrieve the rate that converts from one currency to another, preferring one given in that direction.
+    pub fn rate(&self, from: Currency, to: Currency) -> Option<&ExchangeRate> {
+        self.rates.get(&(from, to)).or_else(|| self.rates.get(&(to, from)))
+    }
+
+    /**
+     * converts an amount into the given currency, rounding in the given mode.
+     * Amounts already in that currency are given back unchanged.
+     */
+    pub fn convert(&self, amount: CurrencyAmount, to: Currency, rounding: RoundingMode) -> Result<CurrencyAmount, CurrencyError> {
+        if amount.currency == to {
+            return Ok(amount)
+        }
+
+        self.rate(amount.currency, to)
+            .ok_or(CurrencyError::MissingRate { from: amount.currency, to })?
+            .convert(amount, rounding)
+    }
+}
\ No newline at end of file
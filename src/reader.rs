@@ -0,0 +1,263 @@
+use std::{collections::VecDeque, io::{self, BufRead}};
+
+use crate::{Type, Header, HeaderOption, Parser, Event, Record, Account, Category, Class, Security, PriceQuote, QifError, ParseOptions};
+use crate::errors::in_record;
+
+/**
+ * structure that reads a QIF file one record at a time,
+ * so that even very large files can be worked through without holding them in memory.
+ *
+ * Anything that implements BufRead can be read, such as a file, stdin, or a decompressed stream:
+ *
+ * ```ignore
+ * let reader = QifReader::new(io::stdin().lock(), &ParseOptions::default());
+ *
+ * for record in reader {
+ *     if let Record::Transaction(transaction) = record? {
+ *         println!("{}", transaction.vendor);
+ *     }
+ * }
+ * ```
+ *
+ * After an error, nothing more is read.
+ * In lenient mode, warnings are kept by the reader,
+ * and can be taken as they come in with take_warnings.
+ */
+pub struct QifReader<R: BufRead> {
+    parser: Parser<Lines<R>>,
+    position: Position,
+    options: ParseOptions,
+    records: VecDeque<Record>,
+    warnings: Vec<QifError>,
+    is_done: bool
+}
+
+impl<R: BufRead> QifReader<R> {
+    pub fn new(reader: R, options: &ParseOptions) -> Self {
+        QifReader {
+            parser: Parser::new(Lines { lines: reader.lines(), error: None }),
+            position: Position::default(),
+            options: options.clone(),
+            records: VecDeque::new(),
+            warnings: vec![],
+            is_done: false
+        }
+    }
+
+    /// retrieve the warnings found so far.
+    pub fn warnings(&self) -> &[QifError] {
+        &self.warnings
+    }
+
+    /// removes and hands back the warnings found so far.
+    pub fn take_warnings(&mut self) -> Vec<QifError> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    fn fail(&mut self, error: QifError) -> Option<Result<Record, QifError>> {
+        self.is_done = true;
+        self.records.clear();
+
+        Some(Err(error))
+    }
+}
+
+impl<R: BufRead> Iterator for QifReader<R> {
+    type Item = Result<Record, QifError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(record) = self.records.pop_front() {
+                return Some(Ok(record))
+            }
+
+            if self.is_done {
+                return None
+            }
+
+            let event = self.parser.next();
+
+            // a record cut short by a failed read must not be handed back as if it were whole.
+            if let Some(error) = self.parser.lines_mut().error.take() {
+                return self.fail(error.into())
+            }
+
+            match event {
+                Some(event) => if let Err(error) = self.position.read(event, &self.options, &mut self.warnings, &mut self.records) {
+                    return self.fail(error)
+                },
+                None => {
+                    self.position.finish(&mut self.records);
+                    self.is_done = true;
+                }
+            }
+        }
+    }
+}
+
+/// the lines of a reader, which stop at the first error, keeping it to be reported.
+struct Lines<R> {
+    lines: io::Lines<R>,
+    error: Option<io::Error>
+}
+
+impl<R: BufRead> Iterator for Lines<R> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.lines.next()? {
+            Ok(line) => Some(line),
+            Err(error) => {
+                self.error = Some(error);
+
+                None
+            }
+        }
+    }
+}
+
+/// the lists that can appear in a QIF file, apart from account sections.
+enum List {
+    Category,
+    Class,
+    Account,
+    Security,
+    Prices
+}
+
+impl List {
+    /// retrieve the list a header (e.g. !Type:Cat) starts, if it starts one.
+    fn from(header: &Header) -> Option<Self> {
+        match header {
+            Header::Category => Some(Self::Category),
+            Header::Class => Some(Self::Class),
+            Header::Account => Some(Self::Account),
+            Header::Security => Some(Self::Security),
+            Header::Prices => Some(Self::Prices),
+            _ => None
+        }
+    }
+}
+
+/**
+ * structure that keeps track of what the records being read are,
+ * as headers change it, turning what the parser finds into records.
+ */
+#[derive(Default)]
+pub(crate) struct Position {
+    section: Option<Type>,
+    list: Option<List>,
+    account: Option<Account>,
+    auto_switch: bool,
+    is_unknown: bool,
+    records: usize
+}
+
+impl Position {
+    /**
+     * reads what the parser found, adding any records it makes to the end of the given queue.
+     * Records are counted from 0 across the whole file, so errors can say which one they were found in.
+     */
+    pub(crate) fn read<L: AsRef<str>>(&mut self, event: Event<L>, options: &ParseOptions, warnings: &mut Vec<QifError>, records: &mut VecDeque<Record>) -> Result<(), QifError> {
+        match event {
+            Event::Header(_, Header::Type(qif_type)) => {
+                self.list = None;
+                self.section = Some(qif_type.clone());
+                self.is_unknown = false;
+
+                records.push_back(match self.account.take() {
+                    Some(account) => Record::AccountStart(account, qif_type),
+                    None => Record::SectionStart(qif_type)
+                });
+            },
+            Event::Header(line, header) => {
+                self.finish(records);
+
+                match header {
+                    Header::Option(HeaderOption::AutoSwitch) => self.auto_switch = true,
+                    Header::Clear(HeaderOption::AutoSwitch) => self.auto_switch = false,
+                    header => if let Some(list) = List::from(&header) {
+                        self.section = None;
+                        self.list = Some(list);
+                        self.is_unknown = false;
+                    } else {
+                        // what follows a header that is not known cannot be read as part of the section or list before it.
+                        if let Header::Unknown(text) = &header {
+                            options.mode.report(QifError::bad_header(text).within(line - 1, self.records), warnings)?;
+
+                            self.section = None;
+                            self.list = None;
+                            self.is_unknown = true;
+                        }
+
+                        records.push_back(Record::Header(header));
+                    }
+                }
+            },
+            Event::Record(line, lines) => {
+                let record = self.records;
+
+                self.records += 1;
+
+                in_record(line - 1, record, warnings, |warnings| self.read_record(&lines, options, warnings, records))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// adds the account waiting for its section to the queue on its own, as nothing more is coming for it.
+    pub(crate) fn finish(&mut self, records: &mut VecDeque<Record>) {
+        if let Some(account) = self.account.take() {
+            records.push_back(Record::Account(account));
+        }
+    }
+
+    /**
+     * reads a record of the list or section being read, with errors given relative to the start of the record.
+     * In lenient mode, a record that cannot be kept is left out,
+     * and records that come before any section or list, or after a header that is not known, are kept as they are.
+     */
+    fn read_record<L: AsRef<str>>(&mut self, lines: &[L], options: &ParseOptions, warnings: &mut Vec<QifError>, records: &mut VecDeque<Record>) -> Result<(), QifError> {
+        let fields = || lines.iter().map(|line| line.as_ref());
+        let mode = &options.mode;
+
+        match &self.list {
+            Some(List::Category) => if let Some(category) = mode.tolerate(Category::from_lines(fields(), options, warnings), warnings)? {
+                records.push_back(Record::Category(category));
+            },
+            Some(List::Class) => if let Some(class) = mode.tolerate(Class::from_lines(fields()), warnings)? {
+                records.push_back(Record::Class(class));
+            },
+            Some(List::Account) => if let Some(account) = mode.tolerate(Account::from_lines(fields(), options, warnings), warnings)? {
+                if self.auto_switch {
+                    records.push_back(Record::Account(account));
+                } else if let Some(previous) = self.account.replace(account) {
+                    records.push_back(Record::Account(previous));
+                }
+            },
+            Some(List::Security) => if let Some(security) = mode.tolerate(Security::from_lines(fields()), warnings)? {
+                records.push_back(Record::Security(security));
+            },
+            Some(List::Prices) => for (index, line) in fields().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+                if let Some(price) = mode.tolerate(PriceQuote::from_str(line, &options.date_format, &options.number_format).map_err(|error| error.within(index, 0)), warnings)? {
+                    records.push_back(Record::Price(price));
+                }
+            },
+            None => if let Some(qif_type) = &self.section {
+                if let Some(record) = mode.tolerate(Record::from_lines(qif_type, fields(), options, warnings), warnings)? {
+                    records.push_back(record);
+                }
+            } else {
+                let lines: Vec<String> = fields().filter(|line| !line.trim().is_empty()).map(String::from).collect();
+
+                if !self.is_unknown {
+                    mode.report(QifError::missing_header(lines.first().map(String::as_str).unwrap_or_default()), warnings)?;
+                }
+                records.push_back(Record::Unknown(lines));
+            }
+        }
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,41 @@
+use crate::{Type, Header, Account, Transaction, InvestmentTransaction, MemorizedTransaction, Invoice, Category, Class, Security, PriceQuote, QifError, ParseOptions};
+
+/**
+ * enumeration for what a QifReader finds in a QIF file, in the order it is found.
+ *
+ * Transactions belong to the section that was started last,
+ * whether by a SectionStart or an AccountStart.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum Record {
+    /// the start of a section that does not belong to a named account, such as !Type:Bank.
+    SectionStart(Type),
+    /// the start of a section that belongs to the given account.
+    AccountStart(Account, Type),
+    /// an account from an account list, which is read while !Option:AutoSwitch is on, or when no section follows it.
+    Account(Account),
+    Transaction(Transaction),
+    InvestmentTransaction(InvestmentTransaction),
+    MemorizedTransaction(MemorizedTransaction),
+    Invoice(Invoice),
+    Category(Category),
+    Class(Class),
+    Security(Security),
+    Price(PriceQuote),
+    /// a header that does not change what is being read, such as !Option:MDY, kept so it can be written back.
+    Header(Header),
+    /// the lines of a record found before any header, which lenient mode keeps so they can be written back.
+    Unknown(Vec<String>)
+}
+
+impl Record {
+    /// parses the lines of a record found in a section of the given type.
+    pub(crate) fn from_lines<'a>(qif_type: &Type, lines: impl Iterator<Item = &'a str>, options: &ParseOptions, warnings: &mut Vec<QifError>) -> Result<Self, QifError> {
+        match qif_type {
+            Type::Investment => InvestmentTransaction::from_lines(lines, options, warnings).map(Self::InvestmentTransaction),
+            Type::Memorized => MemorizedTransaction::from_lines(lines, options, warnings).map(Self::MemorizedTransaction),
+            Type::Invoice => Invoice::from_lines(lines, options, warnings).map(Self::Invoice),
+            _ => Transaction::from_lines(lines, options, warnings).map(Self::Transaction)
+        }
+    }
+}
\ No newline at end of file
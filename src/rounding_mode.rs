@@ -0,0 +1,53 @@
+/// enumeration for the ways an amount can be rounded to fewer decimal places.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum RoundingMode {
+    /// rounds halves to the nearest even digit, also known as banker's rounding.
+    #[default]
+    HalfEven,
+    /// rounds halves away from zero, as is usually done by hand.
+    HalfUp,
+    /// rounds halves toward zero.
+    HalfDown,
+    /// rounds toward zero, dropping the extra digits.
+    TowardZero,
+    /// rounds away from zero.
+    AwayFromZero,
+    /// rounds toward negative infinity.
+    Floor,
+    /// rounds toward positive infinity.
+    Ceiling
+}
+
+impl RoundingMode {
+    /**
+     * divides a value by a positive divisor, rounding the result in this mode.
+     * Returns None if the divisor is not positive.
+     */
+    pub(crate) fn divide(&self, value: i128, divisor: i128) -> Option<i128> {
+        if divisor <= 0 {
+            return None
+        }
+
+        let quotient = value / divisor;
+        let remainder = value % divisor;
+
+        if remainder == 0 {
+            return Some(quotient)
+        }
+
+        let away_from_zero = quotient + value.signum();
+        let twice_remainder = remainder.abs() * 2;
+
+        let rounds_away = match self {
+            Self::HalfEven => twice_remainder > divisor || (twice_remainder == divisor && quotient % 2 != 0),
+            Self::HalfUp => twice_remainder >= divisor,
+            Self::HalfDown => twice_remainder > divisor,
+            Self::TowardZero => false,
+            Self::AwayFromZero => true,
+            Self::Floor => value < 0,
+            Self::Ceiling => value > 0
+        };
+
+        Some(if rounds_away { away_from_zero } else { quotient })
+    }
+}
\ No newline at end of file
@@ -1,15 +1,29 @@
 use std::fmt;
-use regex::Regex;
 
-use crate::{Type, Transaction, DateFormat};
+use crate::{Type, Header, HeaderOption, Parser, Event, Record, Account, Transaction, InvestmentTransaction, MemorizedTransaction, Invoice, DateFormat, NumberFormat, QifError, ParseOptions, Currency, CurrencyAmount, CurrencyError, RateTable, RoundingMode, MergePolicy};
+use crate::errors::in_record;
+use crate::writer::write_to_string;
 
 /** 
- * structure that houses the type and transactions in a QIF file 
+ * structure that houses the type and transactions in a QIF file.
+ * Sections that follow an !Account header keep that account with them,
+ * and any other headers found in the section are kept as unknown fields,
+ * with those found among the records kept apart, so they are written back where they were.
 */
 #[derive(Clone, Debug)]
 pub struct Section {
     pub qif_type: Type,
-    pub transactions: Vec<Transaction>
+    pub account: Option<Account>,
+    pub transactions: Vec<Transaction>,
+    pub investment_transactions: Vec<InvestmentTransaction>,
+    pub memorized_transactions: Vec<MemorizedTransaction>,
+    pub invoices: Vec<Invoice>,
+    /// headers found before the !Type header of the section.
+    pub unknown_fields: Vec<String>,
+    /// headers found after the !Type header, each with the number of records that came before it.
+    pub inner_unknown_fields: Vec<(usize, String)>,
+    /// the currency of the section, when it is not given by its account, which is not written to files.
+    pub currency: Option<Currency>
 }
 
 impl Section {
@@ -18,84 +32,280 @@ impl Section {
      * 
      * The creation is done like this:
      * 
+     * ```ignore
      * let expected_section = Section::builder()
-        .set_type("Bank")
-        .add_transaction(sam_hill)
-        .add_transaction(fake_street)
-        .add_transaction(velociraptor_entertainment)
-        .build().unwrap();
+     *     .set_type("Bank")
+     *     .add_transaction(sam_hill)
+     *     .add_transaction(fake_street)
+     *     .add_transaction(velociraptor_entertainment)
+     *     .build().unwrap();
+     * ```
      */
     pub fn builder() -> SectionBuilder {
         SectionBuilder::new()
     }
 
-    pub fn to_string(&self, df: &DateFormat) -> String {
-        let mut content = format!("!Type:{}\r\n", self.qif_type.to_str());
+    /// retrieve the currency of the section, which is its own if it has one, or else that of its account.
+    pub fn resolved_currency(&self) -> Option<Currency> {
+        self.currency.or_else(|| self.account.as_ref().and_then(|account| account.currency))
+    }
+
+    /**
+     * adds up the amounts of the regular transactions in the section, in the currency of the section.
+     * Gives an error if the section has no currency or the total overflows.
+     */
+    pub fn balance(&self) -> Result<CurrencyAmount, CurrencyError> {
+        let currency = self.resolved_currency().ok_or(CurrencyError::NoCurrency)?;
+
+        self.transactions.iter().try_fold(CurrencyAmount::zero(currency), |balance, transaction| {
+            balance.checked_add(CurrencyAmount::new(transaction.amount, currency))
+        })
+    }
+
+    /// adds up the amounts of the regular transactions in the section, converted into the given currency with the given rates.
+    pub fn balance_in(&self, currency: Currency, rates: &RateTable, rounding: RoundingMode) -> Result<CurrencyAmount, CurrencyError> {
+        rates.convert(self.balance()?, currency, rounding)
+    }
+
+    pub fn to_string(&self, df: &DateFormat, nf: &NumberFormat) -> String {
+        write_to_string(df, nf, |writer| writer.write_section(self))
+    }
+
+    /// parses a section from its !Type header and the records that follow it.
+    pub fn from_str(s: &str, df: &DateFormat, nf: &NumberFormat) -> Result<Self, QifError> {
+        Self::from_str_with(s, &ParseOptions::strict(df, nf)).map(|(section, _)| section)
+    }
+
+    /**
+     * parses a section with the given options, handing back the warnings found along with it.
+     * In lenient mode, records that cannot be kept are left out,
+     * and headers that are not understood are kept as unknown fields.
+     * A section without a !Type header for one of the transaction types is an error in either mode.
+     */
+    pub fn from_str_with(s: &str, options: &ParseOptions) -> Result<(Self, Vec<QifError>), QifError> {
+        let mut section = extract_type(s)
+            .and_then(|qif_type| Section::builder().set_type(qif_type.to_str()).build())
+            .ok_or_else(|| QifError::bad_header(s.lines().find(|line| !line.trim().is_empty()).unwrap_or_default()))?;
+
+        let mut warnings = vec![];
+
+        section.read_records(s, options, &mut warnings)?;
 
-        for transaction in self.transactions.clone() {
-            let transaction_string = format!("{}\r\n\r\n", transaction.to_string(df));
-            content.push_str(&transaction_string);
+        Ok((section, warnings))
+    }
+
+    /**
+     * parses the records found in the given text as the kind of record
+     * this section holds, adding every one of them, even those equal to records already present.
+     * Errors give the line within the given text, and the index of the record among those in it.
+     */
+    pub fn add_record_from(&mut self, s: &str, df: &DateFormat, nf: &NumberFormat) -> Result<(), QifError> {
+        self.read_records(s, &ParseOptions::strict(df, nf), &mut vec![])
+    }
+
+    fn read_records(&mut self, s: &str, options: &ParseOptions, warnings: &mut Vec<QifError>) -> Result<(), QifError> {
+        let mut record_index = 0;
+        let mut in_section = false;
+
+        for event in Parser::new(s.lines()) {
+            match event {
+                Event::Header(line, header) => {
+                    if let Header::Unknown(text) = &header {
+                        options.mode.report(QifError::bad_header(text).within(line - 1, record_index), warnings)?;
+                    }
+
+                    if in_section {
+                        self.add_inner_header(&header)
+                    } else {
+                        in_section = matches!(header, Header::Type(_));
+                        self.add_header(&header)
+                    }
+                },
+                Event::Record(line, lines) => {
+                    in_record(line - 1, record_index, warnings, |warnings| self.add_record(&lines, options, warnings))?;
+
+                    record_index += 1;
+                }
+            }
         }
 
-        content
+        Ok(())
     }
 
-    pub fn from_str(s: &str, df: &DateFormat) -> Option<Self> {
-        let mut builder = Section::builder();
-        
-        builder.set_type(&extract_type(s));
-        
-        if let Ok(transaction) = Transaction::from_str(s, df) {
-            builder.add_transaction(transaction);
+    /// keeps a header found before the !Type header that is not read by the section or QIF document, so it can be written back.
+    pub(crate) fn add_header(&mut self, header: &Header) {
+        if !is_known_header(header) {
+            self.unknown_fields.push(header.to_string());
         }
+    }
 
-        builder.build()
+    /// keeps a header found after the records the section has so far, so it can be written back after them.
+    pub(crate) fn add_inner_header(&mut self, header: &Header) {
+        if !is_known_header(header) {
+            self.inner_unknown_fields.push((self.record_count(), header.to_string()));
+        }
     }
 
-    pub fn add_transaction_if_not_exists(&mut self, transaction: &Transaction) {
-        if let None = self.transactions.iter().find(|&e| e == transaction) {
-            self.transactions.push(transaction.to_owned())
+    /// retrieve the number of records of every kind in the section.
+    pub fn record_count(&self) -> usize {
+        self.transactions.len() + self.investment_transactions.len() + self.memorized_transactions.len() + self.invoices.len()
+    }
+
+    /// parses a record handed over by the parser, adding it as the merge policy of the options allows.
+    /// in lenient mode, a record that cannot be kept is left out, with a warning saying why.
+    pub(crate) fn add_record<L: AsRef<str>>(&mut self, lines: &[L], options: &ParseOptions, warnings: &mut Vec<QifError>) -> Result<(), QifError> {
+        let lines = lines.iter().map(|line| line.as_ref());
+
+        if let Some(record) = options.mode.tolerate(Record::from_lines(&self.qif_type, lines, options, warnings), warnings)? {
+            self.add(record, &options.merge_policy);
         }
+
+        Ok(())
     }
-}
 
-fn extract_type(s: &str) -> String {
-    if let Ok(regex) = Regex::new("!Type:([A-Z|a-z]{4,9})") {
-        if let Some(captures) = regex.captures(s) {
-            let (_, [account_type]) = captures.extract();
+    /// adds a transaction of any kind unless the merge policy drops it as a duplicate, ignoring records that do not go in sections.
+    pub(crate) fn add(&mut self, record: Record, merge_policy: &MergePolicy) {
+        if self.keeps(&record, merge_policy) {
+            self.push(record);
+        }
+    }
+
+    /// checks if the merge policy keeps a record alongside those in this section, rather than dropping it as a duplicate.
+    pub(crate) fn keeps(&self, record: &Record, merge_policy: &MergePolicy) -> bool {
+        match record {
+            Record::Transaction(transaction) => merge_policy.keeps(&self.transactions, transaction),
+            Record::InvestmentTransaction(investment_transaction) => merge_policy.keeps(&self.investment_transactions, investment_transaction),
+            Record::MemorizedTransaction(memorized_transaction) => merge_policy.keeps(&self.memorized_transactions, memorized_transaction),
+            Record::Invoice(invoice) => merge_policy.keeps(&self.invoices, invoice),
+            _ => false
+        }
+    }
 
-            account_type.to_string()
-        } else {
-            String::default()
+    /// adds a transaction of any kind, ignoring records that do not go in sections.
+    pub(crate) fn push(&mut self, record: Record) {
+        match record {
+            Record::Transaction(transaction) => self.transactions.push(transaction),
+            Record::InvestmentTransaction(investment_transaction) => self.investment_transactions.push(investment_transaction),
+            Record::MemorizedTransaction(memorized_transaction) => self.memorized_transactions.push(memorized_transaction),
+            Record::Invoice(invoice) => self.invoices.push(invoice),
+            _ => {}
         }
-    } else {
-        String::default()
+    }
+
+    /**
+     * adds the records and unknown fields of another section to this one,
+     * keeping every record or dropping duplicates as the merge policy says.
+     * Headers found among the records of the other section are moved to follow the records of this one,
+     * staying in front of the first record that came after them and is kept.
+     * Records are compared with those added before them from the other section as well.
+     */
+    pub fn merge(&mut self, section: Section, merge_policy: &MergePolicy) {
+        let mut inner_unknown_fields = section.inner_unknown_fields;
+
+        inner_unknown_fields.sort_by_key(|(position, _)| *position);
+
+        let mut inner_unknown_fields = inner_unknown_fields.into_iter().peekable();
+        let records = section.transactions.into_iter().map(Record::Transaction)
+            .chain(section.investment_transactions.into_iter().map(Record::InvestmentTransaction))
+            .chain(section.memorized_transactions.into_iter().map(Record::MemorizedTransaction))
+            .chain(section.invoices.into_iter().map(Record::Invoice));
+
+        for (index, record) in records.enumerate() {
+            while let Some((_, field)) = inner_unknown_fields.next_if(|(position, _)| *position <= index) {
+                self.inner_unknown_fields.push((self.record_count(), field));
+            }
+
+            self.add(record, merge_policy);
+        }
+
+        let count = self.record_count();
+
+        self.inner_unknown_fields.extend(inner_unknown_fields.map(|(_, field)| (count, field)));
+        self.unknown_fields.extend(section.unknown_fields);
+    }
+
+    /// adds the transaction unless an equal one is already present, as MergePolicy::ExactMatch does.
+    pub fn add_transaction_if_not_exists(&mut self, transaction: &Transaction) {
+        self.add(Record::Transaction(transaction.to_owned()), &MergePolicy::ExactMatch)
+    }
+
+    pub fn add_investment_transaction_if_not_exists(&mut self, investment_transaction: &InvestmentTransaction) {
+        self.add(Record::InvestmentTransaction(investment_transaction.to_owned()), &MergePolicy::ExactMatch)
+    }
+
+    pub fn add_memorized_transaction_if_not_exists(&mut self, memorized_transaction: &MemorizedTransaction) {
+        self.add(Record::MemorizedTransaction(memorized_transaction.to_owned()), &MergePolicy::ExactMatch)
+    }
+
+    pub fn add_invoice_if_not_exists(&mut self, invoice: &Invoice) {
+        self.add(Record::Invoice(invoice.to_owned()), &MergePolicy::ExactMatch)
     }
 }
 
+/// checks if a header is read by the section or the QIF document, rather than kept as an unknown field.
+fn is_known_header(header: &Header) -> bool {
+    matches!(header,
+        Header::Type(_) | Header::Category | Header::Class | Header::Security | Header::Prices | Header::Account |
+        Header::Option(HeaderOption::AutoSwitch) | Header::Clear(HeaderOption::AutoSwitch)
+    )
+}
+
+/// finds the type given by the first section header in the given text.
+fn extract_type(s: &str) -> Option<Type> {
+    s.lines().find_map(|line| match Header::from(line) {
+        Some(Header::Type(qif_type)) => Some(qif_type),
+        _ => None
+    })
+}
+
 impl fmt::Display for Section {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.to_string(&DateFormat::MonthDayFullYear))
+        write!(f, "{}", self.to_string(&DateFormat::MonthDayFullYear, &NumberFormat::default()))
     }
 }
 
 impl PartialEq for Section {
     fn eq(&self, other: &Self) -> bool {
         self.qif_type == other.qif_type &&
-        self.transactions == other.transactions
+        self.account == other.account &&
+        self.transactions == other.transactions &&
+        self.investment_transactions == other.investment_transactions &&
+        self.memorized_transactions == other.memorized_transactions &&
+        self.invoices == other.invoices &&
+        self.unknown_fields == other.unknown_fields &&
+        self.inner_unknown_fields == other.inner_unknown_fields &&
+        self.currency == other.currency
     }
 }
 
 pub struct SectionBuilder {
     qif_type: Option<Type>,
-    transactions: Vec<Transaction>
+    account: Option<Account>,
+    transactions: Vec<Transaction>,
+    investment_transactions: Vec<InvestmentTransaction>,
+    memorized_transactions: Vec<MemorizedTransaction>,
+    invoices: Vec<Invoice>,
+    unknown_fields: Vec<String>,
+    currency: Option<Currency>
+}
+
+impl Default for SectionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SectionBuilder {
     pub fn new() -> Self {
         SectionBuilder {
             qif_type: None,
-            transactions: vec![]
+            account: None,
+            transactions: vec![],
+            investment_transactions: vec![],
+            memorized_transactions: vec![],
+            invoices: vec![],
+            unknown_fields: vec![],
+            currency: None
         }
     }
 
@@ -104,19 +314,53 @@ impl SectionBuilder {
         self
     }
 
+    pub fn set_account(&mut self, account: Account) -> &mut Self {
+        self.account = Some(account);
+        self
+    }
+
+    /// sets the currency from its ISO 4217 code, such as EUR.
+    pub fn set_currency(&mut self, currency: &str) -> &mut Self {
+        self.currency = Currency::from(currency);
+        self
+    }
+
     pub fn add_transaction(&mut self, transaction: Transaction) -> &mut Self {
         self.transactions.push(transaction);
         self
     }
 
+    pub fn add_investment_transaction(&mut self, investment_transaction: InvestmentTransaction) -> &mut Self {
+        self.investment_transactions.push(investment_transaction);
+        self
+    }
+
+    pub fn add_memorized_transaction(&mut self, memorized_transaction: MemorizedTransaction) -> &mut Self {
+        self.memorized_transactions.push(memorized_transaction);
+        self
+    }
+
+    pub fn add_invoice(&mut self, invoice: Invoice) -> &mut Self {
+        self.invoices.push(invoice);
+        self
+    }
+
+    pub fn add_unknown_field(&mut self, field: &str) -> &mut Self {
+        self.unknown_fields.push(field.to_string());
+        self
+    }
+
     pub fn build(&self) -> Option<Section> {
-        if let Some(qif_type) = self.qif_type.clone() {
-            Some(Section { 
-                qif_type, 
-                transactions: self.transactions.clone() 
-            })
-        } else {
-            None
-        }
+        self.qif_type.clone().map(|qif_type| Section { 
+            qif_type, 
+            account: self.account.clone(),
+            transactions: self.transactions.clone(),
+            investment_transactions: self.investment_transactions.clone(),
+            memorized_transactions: self.memorized_transactions.clone(),
+            invoices: self.invoices.clone(),
+            unknown_fields: self.unknown_fields.clone(),
+            inner_unknown_fields: vec![],
+            currency: self.currency
+        })
     }
 }
\ No newline at end of file
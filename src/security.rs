@@ -0,0 +1,166 @@
+use std::fmt;
+
+use crate::QifError;
+use crate::transaction::{drop_first_character_from, is_unknown_field, unknown_fields_to_string};
+
+/// structure that represents an entry in the security list of a QIF file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Security {
+    pub name: String,
+    pub symbol: Option<String>,
+    pub security_type: Option<String>,
+    pub goal: Option<String>,
+    /// lines that were not recognized when parsing, kept in their original order so they can be written back.
+    pub unknown_fields: Vec<String>
+}
+
+impl Security {
+    /**
+     * builder method used to create a security.
+     *
+     * Usage would look like this:
+     *
+     * ```ignore
+     * let velociraptor = Security::builder()
+     *     .set_name("Velociraptor Entertainment")
+     *     .set_symbol("VLCR")
+     *     .set_type("Stock")
+     *     .set_goal("Growth")
+     *     .build().unwrap();
+     * ```
+     */
+    pub fn builder() -> SecurityBuilder {
+        SecurityBuilder::new()
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Self, QifError> {
+        Self::from_lines(s.lines())
+    }
+
+    /// parses the record from its lines, which is how the parser hands records over.
+    pub(crate) fn from_lines<'a>(lines: impl Iterator<Item = &'a str>) -> Result<Self, QifError> {
+        let lines: Vec<&str> = lines.collect();
+        let mut builder = Security::builder();
+
+        for line in &lines {
+            match line {
+                content if content.starts_with("N") => {
+                    builder.set_name(&drop_first_character_from(content));
+                },
+                content if content.starts_with("S") => {
+                    builder.set_symbol(&drop_first_character_from(content));
+                },
+                content if content.starts_with("T") => {
+                    builder.set_type(&drop_first_character_from(content));
+                },
+                content if content.starts_with("G") => {
+                    builder.set_goal(&drop_first_character_from(content));
+                },
+                content => if is_unknown_field(content) {
+                    builder.add_unknown_field(content);
+                }
+            }
+        }
+
+        builder.build().ok_or_else(|| QifError::missing_field('N', &lines))
+    }
+}
+
+impl fmt::Display for Security {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "N{}\r\n", self.name)?;
+
+        if let Some(symbol) = &self.symbol {
+            write!(f, "S{}\r\n", symbol)?;
+        }
+
+        if let Some(security_type) = &self.security_type {
+            write!(f, "T{}\r\n", security_type)?;
+        }
+
+        if let Some(goal) = &self.goal {
+            write!(f, "G{}\r\n", goal)?;
+        }
+
+        write!(f, "{}^", unknown_fields_to_string(&self.unknown_fields))
+    }
+}
+
+pub struct SecurityBuilder {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub security_type: Option<String>,
+    pub goal: Option<String>,
+    pub unknown_fields: Vec<String>
+}
+
+impl Default for SecurityBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SecurityBuilder {
+    pub fn new() -> Self {
+        SecurityBuilder {
+            name: None,
+            symbol: None,
+            security_type: None,
+            goal: None,
+            unknown_fields: vec![]
+        }
+    }
+
+    pub fn set_name(&mut self, name: &str) -> &mut Self {
+        self.name = if name.is_empty() {
+            None
+        } else {
+            Some(name.to_string())
+        };
+        self
+    }
+
+    pub fn set_symbol(&mut self, symbol: &str) -> &mut Self {
+        self.symbol = if symbol.is_empty() {
+            None
+        } else {
+            Some(symbol.to_string())
+        };
+        self
+    }
+
+    pub fn set_type(&mut self, security_type: &str) -> &mut Self {
+        self.security_type = if security_type.is_empty() {
+            None
+        } else {
+            Some(security_type.to_string())
+        };
+        self
+    }
+
+    pub fn set_goal(&mut self, goal: &str) -> &mut Self {
+        self.goal = if goal.is_empty() {
+            None
+        } else {
+            Some(goal.to_string())
+        };
+        self
+    }
+
+    pub fn add_unknown_field(&mut self, field: &str) -> &mut Self {
+        self.unknown_fields.push(field.to_string());
+        self
+    }
+
+    /// creates the security, provided that a name was given.
+    pub fn build(&self) -> Option<Security> {
+        self.name.clone().map(|name| Security {
+            name,
+            symbol: self.symbol.clone(),
+            security_type: self.security_type.clone(),
+            goal: self.goal.clone(),
+            unknown_fields: self.unknown_fields.clone()
+        })
+    }
+}
\ No newline at end of file
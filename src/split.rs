@@ -1,18 +1,20 @@
 use std::fmt;
 
+use crate::{CategoryRef, NumberFormat, Money, RoundingMode};
+
 /// structure that represents a split in a transaction
 #[derive(Clone, Debug)]
 pub struct Split {
-    pub category: Option<String>,
+    pub category: Option<CategoryRef>,
     pub memo: String,
-    pub amount: f64
+    pub amount: Money
 }
 
 impl Split {
     /** method to create a split. 
      * This exists mostly as a convenience initializer, but can be used directly
     */
-    pub fn from(category: Option<String>, memo: String, amount: f64) -> Self {
+    pub fn from(category: Option<CategoryRef>, memo: String, amount: Money) -> Self {
         Split { 
             category, 
             memo, 
@@ -26,27 +28,49 @@ impl Split {
      * 
      * Usage would look like this:
      * 
+     * ```ignore
      * let initial_split = Split::builder()
-        .set_category("Opening Balance")
-        .set_memo("Initial Deposit")
-        .set_amount(450.0)
-        .build().unwrap();
+     *     .set_category("Opening Balance")
+     *     .set_memo("Initial Deposit")
+     *     .set_amount(Money::from("450.00").unwrap())
+     *     .build().unwrap();
+     * ```
      */
     pub fn builder() -> SplitBuilder {
         SplitBuilder::new()
     }
 
-    pub fn to_string(&self) -> String {
-        format!("S{}\r\nE{}\r\n${:.2}", 
-        self.category.clone().unwrap_or(String::default()), 
+    /// retrieve the category portion of the category field, without any class.
+    pub fn category_name(&self) -> Option<String> {
+        self.category.as_ref().and_then(|category| category.name())
+    }
+
+    /// retrieve the class portion of the category field, if there is one.
+    pub fn class_name(&self) -> Option<&str> {
+        self.category.as_ref().and_then(|category| category.class())
+    }
+
+    /// checks if the category field refers to another account.
+    pub fn is_transfer(&self) -> bool {
+        self.category.as_ref().is_some_and(|category| category.is_transfer())
+    }
+
+    /// retrieve the account money is transferred to or from, if this is a transfer.
+    pub fn transfer_account(&self) -> Option<&str> {
+        self.category.as_ref().and_then(|category| category.transfer_account())
+    }
+
+    pub fn to_string(&self, nf: &NumberFormat) -> String {
+        format!("S{}\r\nE{}\r\n${}", 
+        self.category.as_ref().map(|category| category.to_string()).unwrap_or_default(), 
         self.memo, 
-        self.amount)
+        nf.format(self.amount))
     }
 }
 
 impl fmt::Display for Split {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.to_string())
+        write!(f, "{}", self.to_string(&NumberFormat::default()))
     }
 }
 
@@ -59,9 +83,15 @@ impl PartialEq for Split {
 }
 
 pub struct SplitBuilder {
-    pub category: Option<String>,
+    pub category: Option<CategoryRef>,
     pub memo: Option<String>,
-    pub amount: Option<f64>
+    pub amount: Option<Money>
+}
+
+impl Default for SplitBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SplitBuilder {
@@ -74,11 +104,7 @@ impl SplitBuilder {
     }
 
     pub fn set_category(&mut self, category: &str) -> &mut Self {
-        self.category = if category.is_empty() {
-            None
-        } else {
-            Some(category.to_string())
-        };
+        self.category = CategoryRef::from(category);
         self
     }
 
@@ -91,26 +117,21 @@ impl SplitBuilder {
         self
     }
 
-    pub fn set_amount(&mut self, amount: f64) -> &mut Self {
+    pub fn set_amount(&mut self, amount: Money) -> &mut Self {
         self.amount = Some(amount);
         self
     }
 
-    pub fn set_amount_via_percentage(&mut self, amount: f64, percentage: f64) -> &mut Self {
-        let percentage_as_decimal = percentage/100.0;
-
-        self.amount = Some(amount * percentage_as_decimal);
+    /// set the amount to a percentage of the transaction amount, rounded half to even to the decimal places of that amount.
+    pub fn set_amount_via_percentage(&mut self, amount: Money, percentage: Money) -> &mut Self {
+        self.amount = amount.percentage(percentage, RoundingMode::HalfEven);
         self
     }
 
     pub fn build(&self) -> Option<Split> {
-        if let Some(amount) = self.amount {
-            Some(Split::from(
-                self.category.clone(), 
-                self.memo.clone().unwrap_or(String::default()), 
-                amount))
-        } else {
-            None
-        }
+        self.amount.map(|amount| Split::from(
+            self.category.clone(), 
+            self.memo.clone().unwrap_or_default(), 
+            amount))
     }
 }
\ No newline at end of file
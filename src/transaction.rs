@@ -1,6 +1,6 @@
 use chrono::prelude::*;
 
-use crate::{TransactionStatus, Split, DateFormat, TransactionBuildingError, SplitBuilder};
+use crate::{TransactionStatus, CheckNumber, CategoryRef, Split, DateFormat, NumberFormat, TransactionBuildingError, QifError, ParseMode, ParseOptions, SplitBuilder, Money, RoundingMode, CurrencyAmount, ExchangeRate};
 
 use std::fmt;
 use unicode_segmentation::UnicodeSegmentation;
@@ -9,14 +9,21 @@ use unicode_segmentation::UnicodeSegmentation;
 #[derive(Clone, Debug)]
 pub struct Transaction {
     pub date: DateTime<Local>,
-    pub check_number: Option<u32>,
+    pub check_number: Option<CheckNumber>,
     pub vendor: String,
-    pub address: String,
-    pub amount: f64,
-    pub category: Option<String>,
+    /// the lines of the payee's address, which can have up to six lines plus a seventh for a message on printed checks.
+    pub address: Vec<String>,
+    pub amount: Money,
+    /// the amount Quicken writes in the U field, usually with more decimal places than the T amount, if there was one.
+    pub u_amount: Option<Money>,
+    pub category: Option<CategoryRef>,
     pub memo: String,
     pub status: Option<TransactionStatus>,
-    pub splits: Vec<Split>
+    pub splits: Vec<Split>,
+    /// lines that were not recognized when parsing, kept in their original order so they can be written back.
+    pub unknown_fields: Vec<String>,
+    /// the rate the amount was converted at, if it was made in another currency, which is not written to files.
+    pub exchange_rate: Option<ExchangeRate>
 }
 
 impl Transaction {
@@ -27,17 +34,21 @@ impl Transaction {
      * However, this function is mainly used as a convenice initializer, 
      * though it can be used directly.
     */
-    pub fn from(date: DateTime<Local>, check_number: Option<u32>, vendor: String, address: String, amount: f64, category: Option<String>, memo: String, status: Option<TransactionStatus>, splits: Vec<Split>) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn from(date: DateTime<Local>, check_number: Option<CheckNumber>, vendor: String, address: Vec<String>, amount: Money, category: Option<CategoryRef>, memo: String, status: Option<TransactionStatus>, splits: Vec<Split>) -> Self {
         Transaction { 
             date, 
             check_number, 
             vendor, 
             address, 
             amount, 
+            u_amount: None,
             category, 
             memo, 
             status, 
-            splits 
+            splits,
+            unknown_fields: vec![],
+            exchange_rate: None
         }
     }
 
@@ -47,191 +58,162 @@ impl Transaction {
      * 
      * Transactions can then be made like this:
      * 
+     * ```ignore
      * let sam_hill = Transaction::builder()
-        .set_date(&today.format(format.chrono_str()).to_string(), &format)
-        .set_check_number(1260)
-        .set_vendor("Sam Hill Credit Union")
-        .set_address("Sam Hill Credit Union")
-        .set_category("Opening Balance")
-        .set_amount(500.0)
-        .set_memo("Open Account")
-        .set_status("*")
-        .build().unwrap();
+     *     .set_date(&today.format(format.chrono_str()).to_string(), &format)
+     *     .set_check_number(1260)
+     *     .set_vendor("Sam Hill Credit Union")
+     *     .set_address("Sam Hill Credit Union")
+     *     .set_category("Opening Balance")
+     *     .set_amount(Money::from("500.00").unwrap())
+     *     .set_memo("Open Account")
+     *     .set_status("*")
+     *     .build().unwrap();
+     * ```
      */
     pub fn builder() -> TransactionBuilder {
         TransactionBuilder::new()
     }
 
-    pub fn to_string(&self, df: &DateFormat) -> String {
+    /// retrieve the category portion of the category field, without any class.
+    pub fn category_name(&self) -> Option<String> {
+        self.category.as_ref().and_then(|category| category.name())
+    }
+
+    /// retrieve the class portion of the category field, if there is one.
+    pub fn class_name(&self) -> Option<&str> {
+        self.category.as_ref().and_then(|category| category.class())
+    }
+
+    /// checks if the category field refers to another account.
+    pub fn is_transfer(&self) -> bool {
+        self.category.as_ref().is_some_and(|category| category.is_transfer())
+    }
+
+    /// retrieve the account money is transferred to or from, if this is a transfer.
+    pub fn transfer_account(&self) -> Option<&str> {
+        self.category.as_ref().and_then(|category| category.transfer_account())
+    }
+
+    /**
+     * retrieve the amount in the currency the transaction was made in, using its exchange rate,
+     * which converts into the currency of the account the amount is kept in.
+     * Gives None if there is no exchange rate or the amount does not fit.
+     */
+    pub fn original_amount(&self, rounding: RoundingMode) -> Option<CurrencyAmount> {
+        self.exchange_rate.and_then(|exchange_rate| exchange_rate.convert(CurrencyAmount::new(self.amount, exchange_rate.to), rounding).ok())
+    }
+
+    pub fn to_string(&self, df: &DateFormat, nf: &NumberFormat) -> String {
         if self.splits.is_empty() {
-            format!("D{}\r\nT{:.2}\r\nC{}\r\nN{}\r\nP{}\r\nM{}\r\nA{}\r\nL{}\r\n^", 
+            format!("D{}\r\nT{}\r\n{}C{}\r\nN{}\r\nP{}\r\nM{}\r\n{}L{}\r\n{}^", 
                 self.date.format(df.chrono_str()),
-                self.amount,
+                nf.format(self.amount),
+                u_amount_to_string(&self.u_amount, nf),
                 if let Some(status) = &self.status {
                     status.to_str()
                 } else {
                     ""
                 },
-                if let Some(check_number) = self.check_number {
+                if let Some(check_number) = &self.check_number {
                     check_number.to_string()
                 } else {
                     String::default()
                 },
                 self.vendor,
                 self.memo,
-                self.address,
+                address_to_string(&self.address),
                 if let Some(category) = &self.category {
-                    category.to_owned()
+                    category.to_string()
                 } else {
                     String::default()
-                }
+                },
+                unknown_fields_to_string(&self.unknown_fields)
             )
         } else {
-            let mut initial_string = format!("D{}\r\nT{:.2}\r\nC{}\r\nN{}\r\nP{}\r\nM{}\r\nA{}\r\nL{}\r\n", 
-                self.date.format("%m/%d/%Y"),
-                self.amount,
+            let mut initial_string = format!("D{}\r\nT{}\r\n{}C{}\r\nN{}\r\nP{}\r\nM{}\r\n{}L{}\r\n", 
+                self.date.format(df.chrono_str()),
+                nf.format(self.amount),
+                u_amount_to_string(&self.u_amount, nf),
                 if let Some(status) = &self.status {
                     status.to_str()
                 } else {
                     ""
                 },
-                if let Some(check_number) = self.check_number {
+                if let Some(check_number) = &self.check_number {
                     check_number.to_string()
                 } else {
                     String::default()
                 },
                 self.vendor,
                 self.memo,
-                self.address,
+                address_to_string(&self.address),
                 if let Some(category) = &self.category {
-                    category.to_owned()
+                    category.to_string()
                 } else {
                     String::default()
                 }
             );
 
-            for split in self.splits.clone() {
-                if let Some(most_recent_split) = self.splits.last() {
-                    if split == most_recent_split.clone() {
-                        let last_entry = format!("{}\r\n^", split);
-                        initial_string.push_str(&last_entry);
-                    } else {
-                        let split_entry = format!("{}\r\n", split);
-                        initial_string.push_str(&split_entry);
-                    }
-                }
+            for split in &self.splits {
+                let split_entry = format!("{}\r\n", split.to_string(nf));
+                initial_string.push_str(&split_entry);
             }
 
+            initial_string.push_str(&unknown_fields_to_string(&self.unknown_fields));
+            initial_string.push('^');
+
             initial_string
         }
     }
 
-    pub fn from_str(s: &str, df: &DateFormat) -> Result<Self, TransactionBuildingError> {
-        let lines = s.lines();
-        let mut builder = Transaction::builder();
-        let mut split_builders: Vec<SplitBuilder> = vec![];
-
-        for line in lines {
-            match line {
-                content if content.starts_with("D") => {
-                    builder.set_date(&drop_first_character_from(content), df);
-                },
-                content if content.starts_with("T") || content.starts_with("U") => if let Ok(amount) = drop_first_character_from(content).parse::<f64>() {
-                    builder.set_amount(amount);
-                },
-                content if content.starts_with("N") => if let Ok(check_number) = drop_first_character_from(content).parse::<u32>() {
-                    builder.set_check_number(check_number);
-                },
-                content if content.starts_with("P") => {
-                    builder.set_vendor(&drop_first_character_from(content));
-                },
-                content if content.starts_with("A") => {
-                    builder.set_address(&drop_first_character_from(content));
-                },
-                content if content.starts_with("L") => {
-                    builder.set_category(&drop_first_character_from(content));
-                },
-                content if content.starts_with("M") => {
-                    builder.set_memo(&drop_first_character_from(content));
-                },
-                content if content.starts_with("C") => {
-                    builder.set_status(&drop_first_character_from(content));
-                },
-                content if content.starts_with("S") => {
-                    let mut split_builder = Split::builder();
-
-                    split_builder.set_category(&drop_first_character_from(content));
+    pub fn from_str(s: &str, df: &DateFormat, nf: &NumberFormat) -> Result<Self, QifError> {
+        Self::from_str_with(s, &ParseOptions::strict(df, nf)).map(|(transaction, _)| transaction)
+    }
 
-                    split_builders.push(split_builder);
-                },
-                content if content.starts_with("E") => {
-                    if split_builders.is_empty() {
-                        let mut split_builder = Split::builder();
+    /**
+     * parses a transaction with the given options, handing back the warnings found along with it.
+     * In lenient mode, a date or amount that cannot be read is left out,
+     * and a missing date, payee, or amount is filled in with the Unix epoch, nothing, or zero,
+     * so the transaction is always kept.
+     */
+    pub fn from_str_with(s: &str, options: &ParseOptions) -> Result<(Self, Vec<QifError>), QifError> {
+        let mut warnings = vec![];
+        let transaction = Self::from_lines(s.lines(), options, &mut warnings)?;
 
-                        split_builder.set_memo(&drop_first_character_from(content));
+        Ok((transaction, warnings))
+    }
 
-                        split_builders.push(split_builder);
-                    } else {
-                        if let Some(current_split_builder) = split_builders.last_mut() {
-                            current_split_builder.set_memo(&drop_first_character_from(content));
-                        }
-                    }
-                },
-                content if content.starts_with("$") => {
-                    if split_builders.is_empty() {
-                        let mut split_builder = Split::builder();
+    /// parses the record from its lines, which is how the parser hands records over.
+    pub(crate) fn from_lines<'a>(lines: impl Iterator<Item = &'a str>, options: &ParseOptions, warnings: &mut Vec<QifError>) -> Result<Self, QifError> {
+        let lines: Vec<&str> = lines.collect();
+        let mut builder = Transaction::builder();
+        let mut split_builders: Vec<SplitBuilder> = vec![];
 
-                        if let Ok(amount) = drop_first_character_from(content).parse::<f64>() {
-                            split_builder.set_amount(amount);
-                        }
+        parse_lines(&lines, &options.mode, warnings, |line| parse_field(line, &mut builder, &mut split_builders, &options.date_format, &options.number_format))?;
 
-                        split_builders.push(split_builder);
-                    } else {
-                        if let Some(current_split_builder) = split_builders.last_mut() {
-                            if let Ok(amount) = drop_first_character_from(content).parse::<f64>() {
-                                current_split_builder.set_amount(amount);
-                            }
-                        }
-                    }
-                },
-                content if content.starts_with("%") => {
-                    if split_builders.is_empty() {
-                        let mut split_builder = Split::builder();
+        add_splits(&mut builder, split_builders);
 
-                        if let Ok(percentage) = drop_first_character_from(content).parse::<f64>() {
-                            if let Some(amount) = builder.amount {
-                                split_builder.set_amount_via_percentage(amount, percentage);
-                            }
-                        }
+        loop {
+            match builder.build() {
+                Ok(transaction) => return Ok(transaction),
+                Err(error) => {
+                    let missing_field = QifError::missing_field(error.field(), &lines);
 
-                        split_builders.push(split_builder);
+                    if options.mode == ParseMode::Lenient && builder.use_default_for(&error) {
+                        warnings.push(missing_field);
                     } else {
-                        if let Some(current_split_builder) = split_builders.last_mut() {
-                            if let Ok(percentage) = drop_first_character_from(content).parse::<f64>() {
-                                if let Some(amount) = builder.amount {
-                                    current_split_builder.set_amount_via_percentage(amount, percentage);
-                                }
-                            }
-                        }
+                        return Err(missing_field)
                     }
                 }
-                _ => {}
-            }
-        }
-
-        for split_builder in split_builders {
-            if let Some(split) = split_builder.build() {
-                builder.add_split(split);
             }
         }
-
-        builder.build()
     }
 }
 
 impl fmt::Display for Transaction {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.to_string(&DateFormat::MonthDayFullYear))
+        write!(f, "{}", self.to_string(&DateFormat::MonthDayFullYear, &NumberFormat::default()))
     }
 }
 
@@ -242,26 +224,196 @@ impl PartialEq for Transaction {
         self.vendor == other.vendor &&
         self.address == other.address &&
         self.amount == other.amount &&
+        self.u_amount == other.u_amount &&
         self.category == other.category &&
         self.memo == other.memo &&
         self.status == other.status &&
-        self.splits == other.splits
+        self.splits == other.splits &&
+        self.unknown_fields == other.unknown_fields &&
+        self.exchange_rate == other.exchange_rate
     }
 }
 
-fn parse_date(s: &str, format: &DateFormat) -> Option<DateTime<Local>> {
-    if let Ok(date_input) = NaiveDate::parse_from_str(s, format.chrono_str()) {
-        if let Some(datetime) = date_input.and_hms_opt(0, 0, 0) {
-            Some(Local.from_local_datetime(&datetime).unwrap())
-        } else {
-            None
+/**
+ * parses a single line of a transaction record into the given builders.
+ * This is shared by every record type that carries the regular transaction fields.
+ * Dates and amounts that cannot be read, and split fields that come before any split category,
+ * are reported as errors on line 1, to be moved to where the line really is by the caller.
+ */
+pub(crate) fn parse_field(line: &str, builder: &mut TransactionBuilder, split_builders: &mut Vec<SplitBuilder>, df: &DateFormat, nf: &NumberFormat) -> Result<(), QifError> {
+    match line {
+        content if content.starts_with("D") => {
+            builder.date = read_date(line, df)?;
+        },
+        content if content.starts_with("T") => if let Some(amount) = read_amount(line, nf)? {
+            builder.set_amount(amount);
+            check_amounts(builder.amount, builder.u_amount, line)?;
+        },
+        content if content.starts_with("U") => if let Some(amount) = read_amount(line, nf)? {
+            builder.set_u_amount(amount);
+            check_amounts(builder.amount, builder.u_amount, line)?;
+        },
+        content if content.starts_with("N") => {
+            builder.set_check_reference(&drop_first_character_from(content));
+        },
+        content if content.starts_with("P") => {
+            builder.set_vendor(&drop_first_character_from(content));
+        },
+        content if content.starts_with("A") => {
+            builder.add_address_line(&drop_first_character_from(content));
+        },
+        content if content.starts_with("L") => {
+            builder.set_category(&drop_first_character_from(content));
+        },
+        content if content.starts_with("M") => {
+            builder.set_memo(&drop_first_character_from(content));
+        },
+        content if content.starts_with("C") => {
+            builder.set_status(&drop_first_character_from(content));
+        },
+        content if content.starts_with("S") => {
+            let mut split_builder = Split::builder();
+
+            split_builder.set_category(&drop_first_character_from(content));
+
+            split_builders.push(split_builder);
+        },
+        content if content.starts_with("E") => {
+            let current_split_builder = split_builders.last_mut().ok_or_else(|| QifError::orphan_split_field(line))?;
+
+            current_split_builder.set_memo(&drop_first_character_from(content));
+        },
+        content if content.starts_with("$") => {
+            let current_split_builder = split_builders.last_mut().ok_or_else(|| QifError::orphan_split_field(line))?;
+
+            if let Some(amount) = read_amount(line, nf)? {
+                current_split_builder.set_amount(amount);
+            }
+        },
+        content if content.starts_with("%") => {
+            let current_split_builder = split_builders.last_mut().ok_or_else(|| QifError::orphan_split_field(line))?;
+
+            if let Some(percentage) = read_amount(line, nf)? {
+                if let Some(amount) = builder.amount.or(builder.u_amount) {
+                    current_split_builder.set_amount_via_percentage(amount, percentage);
+                }
+            }
+        }
+        content => if is_unknown_field(content) {
+            builder.add_unknown_field(content);
+        }
+    }
+
+    Ok(())
+}
+
+/**
+ * checks that the T and U amounts read so far are the same amount,
+ * where the one with more decimal places may be rounded either way to the decimal places of the other.
+ * The values are kept even when they disagree, so lenient mode can still write both back.
+ */
+pub(crate) fn check_amounts(amount: Option<Money>, u_amount: Option<Money>, line: &str) -> Result<(), QifError> {
+    if let (Some(amount), Some(u_amount)) = (amount, u_amount) {
+        let (precise, rounded) = if u_amount.scale() >= amount.scale() { (u_amount, amount) } else { (amount, u_amount) };
+        let agrees = |rounding| precise.rescale(rounded.scale(), rounding) == Some(rounded);
+
+        if !agrees(RoundingMode::Floor) && !agrees(RoundingMode::Ceiling) {
+            return Err(QifError::amount_conflict(line))
         }
+    }
+
+    Ok(())
+}
+
+/// writes the U field of a transaction, if it has one.
+pub(crate) fn u_amount_to_string(u_amount: &Option<Money>, nf: &NumberFormat) -> String {
+    u_amount.map(|u_amount| format!("U{}\r\n", nf.format(u_amount))).unwrap_or_default()
+}
+
+/// reads the date in a field line, where an empty field means there is no date.
+pub(crate) fn read_date(line: &str, df: &DateFormat) -> Result<Option<DateTime<Local>>, QifError> {
+    let value = drop_first_character_from(line);
+
+    if value.trim().is_empty() {
+        Ok(None)
+    } else {
+        parse_date(&value, df).map(Some).ok_or_else(|| QifError::bad_date(line))
+    }
+}
+
+/// reads the amount in a field line, where an empty field means there is no amount.
+pub(crate) fn read_amount(line: &str, nf: &NumberFormat) -> Result<Option<Money>, QifError> {
+    let value = drop_first_character_from(line);
+
+    if value.trim().is_empty() {
+        Ok(None)
+    } else {
+        nf.parse(&value).map(Some).ok_or_else(|| QifError::bad_amount(line))
+    }
+}
+
+/// reads a value like a price, quantity, or rate in a field line, where an empty field means there is no value.
+pub(crate) fn read_value(line: &str, nf: &NumberFormat) -> Result<Option<f64>, QifError> {
+    let value = drop_first_character_from(line);
+
+    if value.trim().is_empty() {
+        Ok(None)
+    } else {
+        nf.parse_value(&value).map(Some).ok_or_else(|| QifError::bad_amount(line))
+    }
+}
+
+/**
+ * parses every line of a record with the given function, moving any error to the line it was found on.
+ * In lenient mode, errors become warnings and the rest of the lines are still parsed.
+ */
+pub(crate) fn parse_lines<'a>(lines: &[&'a str], mode: &ParseMode, warnings: &mut Vec<QifError>, mut parse: impl FnMut(&'a str) -> Result<(), QifError>) -> Result<(), QifError> {
+    for (index, line) in lines.iter().enumerate() {
+        mode.tolerate(parse(line).map_err(|error| error.within(index, 0)), warnings)?;
+    }
+
+    Ok(())
+}
+
+/// checks if a line that no parser recognized should be kept, which leaves out blank lines, headers, and record ends.
+pub(crate) fn is_unknown_field(line: &str) -> bool {
+    !line.trim().is_empty() && !line.starts_with('!') && !line.starts_with('^')
+}
+
+/// writes unrecognized lines back out verbatim, in the order they were read.
+pub(crate) fn unknown_fields_to_string(unknown_fields: &[String]) -> String {
+    unknown_fields.iter().map(|field| format!("{}\r\n", field)).collect()
+}
+
+pub(crate) fn add_splits(builder: &mut TransactionBuilder, split_builders: Vec<SplitBuilder>) {
+    for split_builder in split_builders {
+        if let Some(split) = split_builder.build() {
+            builder.add_split(split);
+        }
+    }
+}
+
+/// writes each line of an address as its own A field, leaving the field out when there is no address.
+pub(crate) fn address_to_string(address: &[String]) -> String {
+    address.iter().map(|line| format!("A{}\r\n", line)).collect()
+}
+
+/// removes the blank lines at the end of an address, which come from empty A fields.
+pub(crate) fn trim_address(address: &[String]) -> Vec<String> {
+    let length = address.iter().rposition(|line| !line.is_empty()).map_or(0, |index| index + 1);
+
+    address[..length].to_vec()
+}
+
+pub(crate) fn parse_date(s: &str, format: &DateFormat) -> Option<DateTime<Local>> {
+    if let Some(date_input) = format.parse(s) {
+        date_input.and_hms_opt(0, 0, 0).map(|datetime| Local.from_local_datetime(&datetime).unwrap())
    } else {
         None
    }
 }
 
-fn drop_first_character_from(s: &str) -> String {
+pub(crate) fn drop_first_character_from(s: &str) -> String {
     let characters: Vec<String> = s.graphemes(true).map(|s| s.to_owned()).collect();
 
     let content: String = characters[1..].iter().map(|s| s.to_owned()).collect();
@@ -271,14 +423,24 @@ fn drop_first_character_from(s: &str) -> String {
 
 pub struct TransactionBuilder {
     pub date: Option<DateTime<Local>>,
-    pub check_number: Option<u32>,
+    pub check_number: Option<CheckNumber>,
     pub vendor: Option<String>,
-    pub address: Option<String>,
-    pub amount: Option<f64>,
-    pub category: Option<String>,
+    pub address: Vec<String>,
+    pub vendor_as_address: bool,
+    pub amount: Option<Money>,
+    pub u_amount: Option<Money>,
+    pub category: Option<CategoryRef>,
     pub memo: Option<String>,
     pub status: Option<TransactionStatus>,
-    pub splits: Vec<Split>
+    pub splits: Vec<Split>,
+    pub unknown_fields: Vec<String>,
+    pub exchange_rate: Option<ExchangeRate>
+}
+
+impl Default for TransactionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl TransactionBuilder {
@@ -287,30 +449,46 @@ impl TransactionBuilder {
             date: None, 
             check_number: None, 
             vendor: None, 
-            address: None, 
+            address: vec![], 
+            vendor_as_address: false, 
             amount: None, 
+            u_amount: None,
             category: None, 
             memo: None, 
             status: None, 
-            splits: vec![] 
+            splits: vec![],
+            unknown_fields: vec![],
+            exchange_rate: None
         }
     }
 
     // builder functions to set various fields.
     pub fn set_date(&mut self, date: &str, format: &DateFormat) -> &mut Self {
-        self.date = parse_date(date, &format);
+        self.date = parse_date(date, format);
         self
     }
 
     pub fn set_check_number(&mut self, check_number: u32) -> &mut Self {
         self.check_number = if check_number > 0 {
-            Some(check_number)
+            Some(CheckNumber::Number(check_number))
         } else {
             None
         };
         self
     }
 
+    /**
+     * sets the number field from text, such as 1260, ATM, or DEP.
+     * Like set_check_number, a check number of 0 is treated as no number.
+     */
+    pub fn set_check_reference(&mut self, check_reference: &str) -> &mut Self {
+        self.check_number = match CheckNumber::from(check_reference) {
+            Some(CheckNumber::Number(0)) => None,
+            check_number => check_number
+        };
+        self
+    }
+
     pub fn set_vendor(&mut self, vendor: &str) -> &mut Self {
         self.vendor = if vendor.is_empty() {
             None
@@ -320,27 +498,44 @@ impl TransactionBuilder {
         self
     }
 
+    /// sets the address to a single line, replacing any lines that were already added.
     pub fn set_address(&mut self, address: &str) -> &mut Self {
         self.address = if address.is_empty() {
-            self.vendor.clone()
+            vec![]
         } else {
-            Some(String::from(address))
+            vec![String::from(address)]
         };
 
         self
     }
 
-    pub fn set_amount(&mut self, amount: f64) -> &mut Self {
+    pub fn add_address_line(&mut self, line: &str) -> &mut Self {
+        self.address.push(String::from(line));
+        self
+    }
+
+    /**
+     * decides if the vendor should be used as the address
+     * when no address is given, which is only done when asked for.
+     */
+    pub fn set_vendor_as_address(&mut self, vendor_as_address: bool) -> &mut Self {
+        self.vendor_as_address = vendor_as_address;
+        self
+    }
+
+    pub fn set_amount(&mut self, amount: Money) -> &mut Self {
         self.amount = Some(amount);
         self
     }
 
+    /// sets the amount written in the U field, which is used as the amount if there is no T amount.
+    pub fn set_u_amount(&mut self, u_amount: Money) -> &mut Self {
+        self.u_amount = Some(u_amount);
+        self
+    }
+
     pub fn set_category(&mut self, category: &str) -> &mut Self {
-        self.category = if category.is_empty() {
-            None
-        } else {
-            Some(String::from(category))
-        };
+        self.category = CategoryRef::from(category);
 
         self
     }
@@ -360,11 +555,38 @@ impl TransactionBuilder {
         self
     }
 
+    /// sets the rate the amount was converted at from the currency the transaction was made in.
+    pub fn set_exchange_rate(&mut self, exchange_rate: ExchangeRate) -> &mut Self {
+        self.exchange_rate = Some(exchange_rate);
+        self
+    }
+
     pub fn add_split(&mut self, split: Split) -> &mut Self  {
         self.splits.push(split);
         self
     }
 
+    /// keeps a line that is not part of the transaction format, so it can be written back unchanged.
+    pub fn add_unknown_field(&mut self, field: &str) -> &mut Self {
+        self.unknown_fields.push(String::from(field));
+        self
+    }
+
+    /**
+     * fills in the field a build failed for, so that a record can be kept in lenient mode.
+     * Returns false if the error is not for a field this builder can fill in.
+     */
+    pub(crate) fn use_default_for(&mut self, error: &TransactionBuildingError) -> bool {
+        match error {
+            TransactionBuildingError::NoDate => self.date = Some(DateTime::default()),
+            TransactionBuildingError::NoVendor => self.vendor = Some(String::default()),
+            TransactionBuildingError::NoAmount => self.amount = Some(Money::zero()),
+            _ => return false
+        }
+
+        true
+    }
+
     /**
      * this function is used to actually create the transaction.
      * It will return an error if there is no date, vendor, or amount
@@ -373,17 +595,29 @@ impl TransactionBuilder {
     pub fn build(&self) -> Result<Transaction, TransactionBuildingError> {
         if let Some(date) = self.date {
             if let Some(vendor) = self.vendor.clone() {
-                if let Some(amount) = self.amount {
-                    Ok(Transaction::from(
+                if let Some(amount) = self.amount.or(self.u_amount) {
+                    let address = trim_address(&self.address);
+
+                    let mut transaction = Transaction::from(
                         date, 
-                        self.check_number, 
+                        self.check_number.clone(), 
                         vendor.clone(), 
-                        self.address.clone().unwrap_or(vendor.clone()), 
+                        if address.is_empty() && self.vendor_as_address {
+                            vec![vendor]
+                        } else {
+                            address
+                        }, 
                         amount, 
                         self.category.clone(), 
-                        self.memo.clone().unwrap_or(String::default()), 
+                        self.memo.clone().unwrap_or_default(), 
                         self.status.clone(), 
-                        self.splits.clone()))
+                        self.splits.clone());
+
+                    transaction.u_amount = self.u_amount;
+                    transaction.exchange_rate = self.exchange_rate;
+                    transaction.unknown_fields = self.unknown_fields.clone();
+
+                    Ok(transaction)
                 } else {
                     Err(TransactionBuildingError::NoAmount)
                 }
@@ -0,0 +1,264 @@
+use std::io::{self, Write};
+
+use crate::{QIF, QifItem, Section, Type, Header, HeaderOption, Record, Account, Transaction, InvestmentTransaction, MemorizedTransaction, Invoice, Category, Class, Security, PriceQuote, DateFormat, NumberFormat};
+
+/**
+ * structure that writes a QIF file one record at a time,
+ * straight to a file, stdout, or a buffer.
+ *
+ * The headers for lists, such as !Type:Cat, are written when the first entry of a list is,
+ * so records can be written as they come, including those read by a QifReader:
+ *
+ * ```ignore
+ * let mut writer = QifWriter::new(io::stdout().lock(), &DateFormat::MonthDayFullYear, &NumberFormat::default());
+ *
+ * writer.start_section(&Type::Bank)?;
+ * writer.write_transaction(&transaction)?;
+ * writer.finish()?;
+ * ```
+ *
+ * Records of a section should follow the start of that section,
+ * and finish should be called once everything is written,
+ * to end any account list that is still open and flush the output.
+ */
+pub struct QifWriter<W: Write> {
+    writer: W,
+    date_format: DateFormat,
+    number_format: NumberFormat,
+    current: Option<Header>,
+    auto_switch: bool
+}
+
+impl<W: Write> QifWriter<W> {
+    pub fn new(writer: W, df: &DateFormat, nf: &NumberFormat) -> Self {
+        QifWriter {
+            writer,
+            date_format: df.clone(),
+            number_format: nf.clone(),
+            current: None,
+            auto_switch: false
+        }
+    }
+
+    /// writes a header as it is, keeping track of the list or section it starts.
+    pub fn write_header(&mut self, header: &Header) -> io::Result<()> {
+        match header {
+            Header::Option(HeaderOption::AutoSwitch) => self.auto_switch = true,
+            Header::Clear(HeaderOption::AutoSwitch) => self.auto_switch = false,
+            Header::Type(_) | Header::Category | Header::Class | Header::Security | Header::Prices | Header::Account => self.current = Some(header.clone()),
+            _ => {}
+        }
+
+        write!(self.writer, "{}\r\n", header)
+    }
+
+    /// writes the header that starts a section that does not belong to a named account.
+    pub fn start_section(&mut self, qif_type: &Type) -> io::Result<()> {
+        self.end_account_list()?;
+        self.write_header(&Header::Type(qif_type.clone()))
+    }
+
+    /// writes the account, followed by the header that starts its section.
+    pub fn start_account_section(&mut self, account: &Account, qif_type: &Type) -> io::Result<()> {
+        self.write_account_header(account)?;
+        self.write_header(&Header::Type(qif_type.clone()))
+    }
+
+    /// writes a whole section, starting with its account and the headers it kept.
+    pub fn write_section(&mut self, section: &Section) -> io::Result<()> {
+        if let Some(account) = &section.account {
+            self.write_account_header(account)?;
+        } else {
+            self.end_account_list()?;
+        }
+
+        for field in &section.unknown_fields {
+            write!(self.writer, "{}\r\n", field)?;
+        }
+
+        self.write_header(&Header::Type(section.qif_type.clone()))?;
+
+        let mut position = 0;
+
+        for transaction in &section.transactions {
+            self.write_inner_fields(section, position)?;
+            self.write_transaction(transaction)?;
+            position += 1;
+        }
+
+        for investment_transaction in &section.investment_transactions {
+            self.write_inner_fields(section, position)?;
+            self.write_investment_transaction(investment_transaction)?;
+            position += 1;
+        }
+
+        for memorized_transaction in &section.memorized_transactions {
+            self.write_inner_fields(section, position)?;
+            self.write_memorized_transaction(memorized_transaction)?;
+            position += 1;
+        }
+
+        for invoice in &section.invoices {
+            self.write_inner_fields(section, position)?;
+            self.write_invoice(invoice)?;
+            position += 1;
+        }
+
+        for (_, field) in section.inner_unknown_fields.iter().filter(|(field_position, _)| *field_position >= position) {
+            write!(self.writer, "{}\r\n", field)?;
+        }
+
+        Ok(())
+    }
+
+    /// writes the headers a section kept from among its records that came before the record at the given position.
+    fn write_inner_fields(&mut self, section: &Section, position: usize) -> io::Result<()> {
+        for (_, field) in section.inner_unknown_fields.iter().filter(|(field_position, _)| *field_position == position) {
+            write!(self.writer, "{}\r\n", field)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn write_transaction(&mut self, transaction: &Transaction) -> io::Result<()> {
+        write!(self.writer, "{}\r\n\r\n", transaction.to_string(&self.date_format, &self.number_format))
+    }
+
+    pub fn write_investment_transaction(&mut self, investment_transaction: &InvestmentTransaction) -> io::Result<()> {
+        write!(self.writer, "{}\r\n\r\n", investment_transaction.to_string(&self.date_format, &self.number_format))
+    }
+
+    pub fn write_memorized_transaction(&mut self, memorized_transaction: &MemorizedTransaction) -> io::Result<()> {
+        write!(self.writer, "{}\r\n\r\n", memorized_transaction.to_string(&self.date_format, &self.number_format))
+    }
+
+    pub fn write_invoice(&mut self, invoice: &Invoice) -> io::Result<()> {
+        write!(self.writer, "{}\r\n\r\n", invoice.to_string(&self.date_format, &self.number_format))
+    }
+
+    /// writes an account to the account list, which is started with !Option:AutoSwitch if it is not already open.
+    pub fn write_account(&mut self, account: &Account) -> io::Result<()> {
+        if !self.auto_switch || self.current != Some(Header::Account) {
+            if !self.auto_switch {
+                self.write_header(&Header::Option(HeaderOption::AutoSwitch))?;
+            }
+
+            self.write_header(&Header::Account)?;
+        }
+
+        write!(self.writer, "{}\r\n\r\n", account.to_string(&self.date_format, &self.number_format))
+    }
+
+    pub fn write_category(&mut self, category: &Category) -> io::Result<()> {
+        self.start_list(Header::Category)?;
+
+        write!(self.writer, "{}\r\n\r\n", category.to_string(&self.number_format))
+    }
+
+    pub fn write_class(&mut self, class: &Class) -> io::Result<()> {
+        self.start_list(Header::Class)?;
+
+        write!(self.writer, "{}\r\n\r\n", class)
+    }
+
+    pub fn write_security(&mut self, security: &Security) -> io::Result<()> {
+        self.start_list(Header::Security)?;
+
+        write!(self.writer, "{}\r\n\r\n", security)
+    }
+
+    pub fn write_price(&mut self, price: &PriceQuote) -> io::Result<()> {
+        self.start_list(Header::Prices)?;
+
+        write!(self.writer, "{}\r\n^\r\n", price.to_string(&self.date_format, &self.number_format))
+    }
+
+    /// writes the lines of a record that is not part of a section or list as they are.
+    pub fn write_unknown(&mut self, lines: &[String]) -> io::Result<()> {
+        for line in lines {
+            write!(self.writer, "{}\r\n", line)?;
+        }
+
+        write!(self.writer, "^\r\n\r\n")
+    }
+
+    /// writes a record, such as one read by a QifReader.
+    pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
+        match record {
+            Record::SectionStart(qif_type) => self.start_section(qif_type),
+            Record::AccountStart(account, qif_type) => self.start_account_section(account, qif_type),
+            Record::Account(account) => self.write_account(account),
+            Record::Transaction(transaction) => self.write_transaction(transaction),
+            Record::InvestmentTransaction(investment_transaction) => self.write_investment_transaction(investment_transaction),
+            Record::MemorizedTransaction(memorized_transaction) => self.write_memorized_transaction(memorized_transaction),
+            Record::Invoice(invoice) => self.write_invoice(invoice),
+            Record::Category(category) => self.write_category(category),
+            Record::Class(class) => self.write_class(class),
+            Record::Security(security) => self.write_security(security),
+            Record::Price(price) => self.write_price(price),
+            Record::Header(header) => self.write_header(header),
+            Record::Unknown(lines) => self.write_unknown(lines)
+        }
+    }
+
+    /// writes a part of a document, such as a section or a list.
+    pub fn write_item(&mut self, item: &QifItem) -> io::Result<()> {
+        match item {
+            QifItem::Section(section) => self.write_section(section),
+            QifItem::Accounts(accounts) => accounts.iter().try_for_each(|account| self.write_account(account)),
+            QifItem::Categories(categories) => categories.iter().try_for_each(|category| self.write_category(category)),
+            QifItem::Classes(classes) => classes.iter().try_for_each(|class| self.write_class(class)),
+            QifItem::Securities(securities) => securities.iter().try_for_each(|security| self.write_security(security)),
+            QifItem::Prices(prices) => prices.iter().try_for_each(|price| self.write_price(price)),
+            QifItem::Header(header) => self.write_header(header),
+            QifItem::Unknown(lines) => self.write_unknown(lines)
+        }
+    }
+
+    /// writes a whole document, with its items in the order they are kept.
+    pub fn write_qif(&mut self, qif: &QIF) -> io::Result<()> {
+        qif.items.iter().try_for_each(|item| self.write_item(item))
+    }
+
+    /// ends any account list that is still open, and flushes the output, handing back what was written to.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.end_account_list()?;
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
+
+    fn start_list(&mut self, header: Header) -> io::Result<()> {
+        if self.current.as_ref() != Some(&header) {
+            self.end_account_list()?;
+            self.write_header(&header)?;
+        }
+
+        Ok(())
+    }
+
+    /// writes an account that the section after it belongs to.
+    fn write_account_header(&mut self, account: &Account) -> io::Result<()> {
+        self.end_account_list()?;
+        self.write_header(&Header::Account)?;
+
+        write!(self.writer, "{}\r\n", account.to_string(&self.date_format, &self.number_format))
+    }
+
+    fn end_account_list(&mut self) -> io::Result<()> {
+        if self.auto_switch {
+            self.write_header(&Header::Clear(HeaderOption::AutoSwitch))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// writes to a string with a QifWriter, which cannot fail since nothing is written outside of memory.
+pub(crate) fn write_to_string(df: &DateFormat, nf: &NumberFormat, write: impl FnOnce(&mut QifWriter<Vec<u8>>) -> io::Result<()>) -> String {
+    let mut writer = QifWriter::new(vec![], df, nf);
+
+    write(&mut writer).and_then(|_| writer.finish())
+        .map(|bytes| String::from_utf8(bytes).expect("records are written from strings"))
+        .expect("writing to memory does not fail")
+}
\ No newline at end of file